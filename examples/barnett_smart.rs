@@ -0,0 +1,93 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    babyjubjub::Point,
+    barnett_smart::{
+        encode_card, Ciphertext, KeyAggregator, KeyPair, RegistrationError, UnmaskShares,
+    },
+    context::GameContext,
+    key_registration_circuit::{registration_tag, KeyRegistrationCircuit},
+    unmask_share_circuit::UnmaskShareCircuit,
+};
+
+const DECK_SIZE: u64 = 52;
+
+fn main() {
+    let k = 17;
+    let table_id = Fr::from(0x7ab1e);
+    let context = GameContext::new(Fr::from(0x5e55), 0);
+
+    let players = (0..3)
+        .map(|i| KeyPair::from(Fr::from(0x1000 + i)))
+        .collect::<Vec<_>>();
+
+    // every player proves knowledge of their key share before it joins the joint key
+    let mut aggregator = KeyAggregator::new(table_id);
+    for (seat, player) in players.iter().enumerate() {
+        let circuit = KeyRegistrationCircuit {
+            table_id,
+            seat: seat as u64,
+            secret_key: player.secret_key,
+            context,
+        };
+        let instances = circuit.instances();
+        MockProver::run(k, &circuit, instances.clone())
+            .unwrap()
+            .assert_satisfied();
+        // the proof does not verify for another move
+        let mut replayed = instances.clone();
+        *replayed[0].last_mut().unwrap() = context.next().digest();
+        assert!(MockProver::run(k, &circuit, replayed)
+            .unwrap()
+            .verify()
+            .is_err());
+        aggregator
+            .register(seat as u64, circuit.public_key(), instances[0][2])
+            .unwrap();
+    }
+
+    // a key with a component of order 2 is rejected even with a valid tag
+    let order_two = Point {
+        x: Fr::from(0),
+        y: -Fr::from(1),
+    };
+    let small_order = players[0].public_key.add(&order_two);
+    let seat = players.len() as u64;
+    assert_eq!(
+        aggregator.register(
+            seat,
+            small_order,
+            registration_tag(table_id, seat, small_order)
+        ),
+        Err(RegistrationError::InvalidKey)
+    );
+    let joint_key = aggregator.joint_key();
+
+    // every player remasks the card in turn
+    let card = 7;
+    let mut ciphertext = Ciphertext::mask(encode_card(card), joint_key, Fr::from(0x42));
+    for i in 0..players.len() {
+        ciphertext = ciphertext.remask(joint_key, Fr::from(0x100 + i as u64));
+    }
+
+    let mut shares = UnmaskShares::new(ciphertext, players.len());
+    for (i, player) in players.iter().enumerate() {
+        assert_eq!(shares.reveal(DECK_SIZE), None);
+
+        let circuit = UnmaskShareCircuit {
+            secret_key: player.secret_key,
+            c1: ciphertext.c1,
+            context: context.advance(1 + i as u64),
+        };
+        MockProver::run(k, &circuit, circuit.instances())
+            .unwrap()
+            .assert_satisfied();
+        shares.add_share(i, circuit.share());
+    }
+
+    assert!(shares.is_complete());
+    assert_eq!(shares.reveal(DECK_SIZE), Some(card));
+    println!("revealed card: {card}");
+}
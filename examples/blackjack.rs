@@ -0,0 +1,69 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    blackjack::{Outcome, BLACKJACK},
+    blackjack_circuit::{BlackjackCircuit, BlackjackMode},
+    context::GameContext,
+};
+
+fn main() {
+    let k = 12;
+    let context = GameContext::new(Fr::from(0x5e55), 4);
+
+    // ace and king, only the outcome is exposed
+    let natural = BlackjackCircuit::<Fr, 2> {
+        hand: [12, 24],
+        hand_salt: Fr::from(0x5a17),
+        mode: BlackjackMode::Outcome,
+        context,
+    };
+    let instances = natural.instances();
+    MockProver::run(k, &natural, instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    assert_eq!(instances[0][1], Fr::from(Outcome::Blackjack as u64));
+    // the proof does not verify for another move
+    let mut replayed = instances.clone();
+    *replayed[0].last_mut().unwrap() = context.next().digest();
+    assert!(MockProver::run(k, &natural, replayed)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // ace and two sixes, the ace counts as 1
+    let soft = BlackjackCircuit::<Fr, 3> {
+        hand: [12, 4, 17],
+        hand_salt: Fr::from(0x5a18),
+        mode: BlackjackMode::Value,
+        context,
+    };
+    let instances = soft.instances();
+    MockProver::run(k, &soft, instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    assert_eq!(instances[0][1], Fr::from(13));
+
+    // the player cannot claim 21
+    let mut instances = instances;
+    instances[0][1] = Fr::from(BLACKJACK);
+    assert!(MockProver::run(k, &soft, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // not even with a hand that swaps the second six for a four
+    let swapped = BlackjackCircuit {
+        hand: [12, 4, 2],
+        ..soft.clone()
+    };
+    assert_eq!(swapped.result(), BLACKJACK);
+    let mut instances = swapped.instances();
+    instances[0][0] = soft.instances()[0][0];
+    assert!(MockProver::run(k, &swapped, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("blackjack hands verified");
+}
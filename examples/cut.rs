@@ -0,0 +1,61 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext, cut_circuit::CutCircuit, distinct_single_key::pack_cards,
+    key_opening_circuit::KeyOpeningCircuit, utils::randomize,
+};
+
+fn main() {
+    let k = 10;
+    let context = GameContext::new(Fr::from(0x5e55), 1);
+
+    let cut = CutCircuit::<Fr, 8, 1, 31> {
+        deck: randomize(std::array::from_fn(|i| i as u64), 20),
+        offset: 3,
+        offset_salt: Fr::from(0xc07),
+        context,
+    };
+    let instances = cut.instances();
+    MockProver::run(k, &cut, instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    // the proof does not verify for another move
+    let mut replayed = instances.clone();
+    *replayed[0].last_mut().unwrap() = context.next().digest();
+    assert!(MockProver::run(k, &cut, replayed)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // the offset is opened later in the session like a DSK key
+    let opening = KeyOpeningCircuit::<Fr, 8> {
+        key: cut.offset,
+        key_salt: cut.offset_salt,
+        context: context.advance(4),
+    };
+    let opening_instances = opening.instances();
+    MockProver::run(k, &opening, opening_instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    assert_eq!(
+        opening_instances[0][0],
+        instances[0][instances[0].len() - 2]
+    );
+
+    // the output must be the deck cut at the committed offset
+    let miscut = CutCircuit {
+        offset: 4,
+        ..cut.clone()
+    };
+    let output = pack_cards::<Fr, 1, 31>(&miscut.cut_deck());
+    let mut instances = instances;
+    let num_slots = output.len();
+    instances[0][num_slots..2 * num_slots].copy_from_slice(&output);
+    assert!(MockProver::run(k, &cut, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("cut deck: {:?}", cut.cut_deck());
+}
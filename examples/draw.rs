@@ -0,0 +1,67 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext,
+    deck_state::{DeckState, DrawAccumulator, DrawError},
+    draw_circuit::DrawCircuit,
+    hand_chip::hand_commitment,
+    utils::randomize,
+};
+
+fn main() {
+    let k = 11;
+    let context = GameContext::new(Fr::from(0x5e55), 2);
+
+    let cards = randomize(std::array::from_fn::<u64, 8, _>(|i| i as u64), 20);
+    let mut deck = DeckState::new(context.session_id, &cards, Fr::from(0xdec), 3);
+    let mut accumulator = DrawAccumulator::new(deck.root());
+
+    let mut proofs = vec![];
+    for (i, position) in [5, 2].into_iter().enumerate() {
+        let draw = deck.draw(position).unwrap();
+        let circuit = DrawCircuit::<Fr, 3>::new(
+            &draw,
+            deck.deck_salt(),
+            Fr::from(0xca7d + i as u64),
+            context.advance(i as u64),
+        );
+        let instances = circuit.instances();
+        MockProver::run(k, &circuit, instances.clone())
+            .unwrap()
+            .assert_satisfied();
+        assert_eq!(
+            instances[0][..3],
+            [draw.old_root, draw.new_root, draw.nullifier]
+        );
+        accumulator
+            .apply(instances[0][0], instances[0][1], instances[0][2])
+            .unwrap();
+        proofs.push((circuit, instances));
+    }
+    assert_eq!(accumulator.root(), deck.root());
+    assert_eq!(deck.remaining(), vec![0, 1, 3, 4, 6, 7]);
+
+    // a drawn position cannot be drawn again, and a replayed draw is rejected
+    assert_eq!(deck.draw(5).unwrap_err(), DrawError::InvalidPosition);
+    let (first, first_instances) = &proofs[0];
+    assert_eq!(
+        accumulator.apply(
+            first_instances[0][0],
+            first_instances[0][1],
+            first_instances[0][2]
+        ),
+        Err(DrawError::StaleRoot)
+    );
+
+    // the drawn card cannot be swapped for another one
+    let mut instances = first_instances.clone();
+    let other = cards[(first.position + 1) % cards.len()];
+    instances[0][3] = hand_commitment(context.session_id, [other], first.card_salt);
+    assert!(MockProver::run(k, first, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("drawn cards: {:?}", [proofs[0].0.card, proofs[1].0.card]);
+}
@@ -0,0 +1,191 @@
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        halo2curves::bn256::Fr,
+        plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+use zk_card::{
+    babyjubjub::Point,
+    context::GameContext,
+    ecc_chip::EccChip,
+    eddsa::{verify, Signature, SigningKey},
+    eddsa_chip::{AssignedSignature, EddsaChip},
+    gate_chip::GateChip,
+    poseidon_chip::{poseidon_circom, CircomPoseidonChip},
+    signed_action_circuit::{action_message, SignedActionCircuit},
+};
+
+/// Verifies a signature of a public message with [`EddsaChip`], instances are
+/// `[public_key.x, public_key.y, message]`.
+#[derive(Clone)]
+struct SignatureCircuit {
+    public_key: Point<Fr>,
+    message: Fr,
+    signature: Signature<Fr>,
+}
+
+impl Circuit<Fr> for SignatureCircuit {
+    type Config = (GateChip<Fr>, CircomPoseidonChip<Fr, 6, 5>, Column<Instance>);
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = CircomPoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        (gate_chip, poseidon_chip, instance)
+    }
+
+    fn synthesize(
+        &self,
+        (gate_chip, poseidon_chip, instance): Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let ecc_chip = EccChip::from(gate_chip.clone());
+
+        let public_key = ecc_chip.load_private(
+            layouter.namespace(|| "load public key"),
+            Value::known(self.public_key),
+        )?;
+        let message = gate_chip.load_private(
+            layouter.namespace(|| "load message"),
+            Value::known(self.message),
+        )?;
+        let signature = AssignedSignature {
+            r: ecc_chip.load_private(
+                layouter.namespace(|| "load r"),
+                Value::known(self.signature.r),
+            )?,
+            s: gate_chip.load_private(
+                layouter.namespace(|| "load s"),
+                Value::known(self.signature.s),
+            )?,
+        };
+        EddsaChip::from(gate_chip, poseidon_chip).verify(
+            layouter.namespace(|| "verify signature"),
+            public_key.clone(),
+            message.clone(),
+            signature,
+        )?;
+
+        layouter.constrain_instance(public_key.x.cell(), instance, 0)?;
+        layouter.constrain_instance(public_key.y.cell(), instance, 1)?;
+        layouter.constrain_instance(message.cell(), instance, 2)?;
+        Ok(())
+    }
+}
+
+impl CircuitExt<Fr> for SignatureCircuit {
+    fn instances(&self) -> Vec<Vec<Fr>> {
+        vec![vec![self.public_key.x, self.public_key.y, self.message]]
+    }
+}
+
+fn fr(value: &str) -> Fr {
+    Fr::from_str_vartime(value).unwrap()
+}
+
+fn main() {
+    let k = 17;
+
+    // circomlib's BabyJubJub generator, eight times it is Base8
+    let generator = Point {
+        x: fr("995203441582195749578291179787384436505546430278305826713579947235728471134"),
+        y: fr("5472060717959818805561601436314318772137091100104008585924551046643952123905"),
+    };
+    assert_eq!(generator.mul(Fr::from(8)), Point::generator());
+
+    // circomlibjs `poseidon([1, 2])`
+    assert_eq!(
+        poseidon_circom::<_, 3, 2>([Fr::from(1), Fr::from(2)]),
+        fr("7853200120776062878684798364095072458815029376092732009249414926327459813530")
+    );
+
+    // circomlibjs EdDSA-Poseidon test vector, the message is the bytes 00 01 .. 09 00 00
+    // read in little-endian
+    let vector = SignatureCircuit {
+        public_key: Point {
+            x: fr("13277427435165878497778222415993513565335242147425444199013288855685581939618"),
+            y: fr("13622229784656158136036771217484571176836296686641868549125388198837476602820"),
+        },
+        message: fr("42649378395939397566720"),
+        signature: Signature {
+            r: Point {
+                x: fr(
+                    "11384336176656855268977457483345535180380036354188103142384839473266348197733",
+                ),
+                y: fr(
+                    "15383486972088797283337779941324724402501462225528836549661220478783371668959",
+                ),
+            },
+            s: fr("1672775540645840396591609181675628451599263765380031905495115170613215233181"),
+        },
+    };
+    assert!(verify(vector.public_key, vector.message, &vector.signature));
+    assert!(!verify(
+        vector.public_key,
+        vector.message + Fr::from(1),
+        &vector.signature
+    ));
+    MockProver::run(k, &vector, vector.instances())
+        .unwrap()
+        .assert_satisfied();
+    let mut instances = vector.instances();
+    instances[0][2] += Fr::from(1);
+    assert!(MockProver::run(k, &vector, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // a player signs an action for one move of the session
+    let key = SigningKey::from(Fr::from(0x5eed));
+    let action = Fr::from(0xbe7);
+    let context = GameContext::new(Fr::from(0x5e55), 3);
+    let signature = key.sign(action_message(action, &context));
+    assert!(verify(
+        key.public_key,
+        action_message(action, &context),
+        &signature
+    ));
+
+    let circuit = SignedActionCircuit {
+        public_key: key.public_key,
+        action,
+        signature,
+        context,
+    };
+    MockProver::run(k, &circuit, circuit.instances())
+        .unwrap()
+        .assert_satisfied();
+
+    // the proof does not hold for another action
+    let mut instances = circuit.instances();
+    instances[0][2] = action + Fr::from(1);
+    assert!(MockProver::run(k, &circuit, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // and the signature does not carry over to the next move
+    let replayed = SignedActionCircuit {
+        context: context.next(),
+        ..circuit
+    };
+    assert!(MockProver::run(k, &replayed, replayed.instances())
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("signed action verified");
+}
@@ -0,0 +1,180 @@
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        halo2curves::bn256::Fr,
+        plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+    },
+    CircuitExt,
+};
+use zk_card::{
+    babyjubjub::Point,
+    cipher_chip::CipherChip,
+    context::GameContext,
+    encrypted_hand_circuit::EncryptedHandCircuit,
+    gate_chip::GateChip,
+    hand_chip::hand_commitment,
+    poseidon_chip::PoseidonChip,
+    poseidon_cipher::{decrypt, shared_key, CipherError},
+};
+
+const HAND_SIZE: usize = 5;
+
+/// Decrypts a ciphertext with [`CipherChip::decrypt`] under a private key, instances are
+/// `[nonce, ciphertext.., message..]`.
+#[derive(Clone)]
+struct DecryptionCircuit {
+    key: Fr,
+    nonce: Fr,
+    ciphertext: Vec<Fr>,
+    message: Vec<Fr>,
+}
+
+impl Circuit<Fr> for DecryptionCircuit {
+    type Config = (GateChip<Fr>, PoseidonChip<Fr, 2>, Column<Instance>);
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        (gate_chip, poseidon_chip, instance)
+    }
+
+    fn synthesize(
+        &self,
+        (gate_chip, poseidon_chip, instance): Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let key =
+            gate_chip.load_private(layouter.namespace(|| "load key"), Value::known(self.key))?;
+        let nonce = gate_chip.load_private(
+            layouter.namespace(|| "load nonce"),
+            Value::known(self.nonce),
+        )?;
+        let mut ciphertext = vec![];
+        for value in self.ciphertext.iter() {
+            ciphertext.push(gate_chip.load_private(
+                layouter.namespace(|| "load ciphertext"),
+                Value::known(*value),
+            )?);
+        }
+        let message = CipherChip::from(gate_chip, poseidon_chip).decrypt(
+            layouter.namespace(|| "decrypt"),
+            key,
+            nonce.clone(),
+            &ciphertext,
+        )?;
+
+        layouter.constrain_instance(nonce.cell(), instance, 0)?;
+        for (i, cell) in ciphertext.iter().chain(message.iter()).enumerate() {
+            layouter.constrain_instance(cell.cell(), instance, 1 + i)?;
+        }
+        Ok(())
+    }
+}
+
+impl CircuitExt<Fr> for DecryptionCircuit {
+    fn instances(&self) -> Vec<Vec<Fr>> {
+        let mut values = vec![self.nonce];
+        values.extend(self.ciphertext.iter().chain(self.message.iter()));
+        vec![values]
+    }
+}
+
+fn main() {
+    let k = 17;
+
+    let secret_key = Fr::from(0x5eed);
+    let circuit = EncryptedHandCircuit::<Fr, HAND_SIZE> {
+        hand: [3, 17, 25, 40, 51],
+        hand_salt: Fr::from(0x5a17),
+        public_key: Point::generator().mul(secret_key),
+        ephemeral_secret: Fr::from(0xe1),
+        nonce: Fr::from(1),
+        context: GameContext::new(Fr::from(0x5e55), 2),
+    };
+    let instances = circuit.instances();
+    MockProver::run(k, &circuit, instances.clone())
+        .unwrap()
+        .assert_satisfied();
+
+    // the player decrypts the hand and can open the commitment
+    let ephemeral_key = Point {
+        x: instances[0][2],
+        y: instances[0][3],
+    };
+    let nonce = instances[0][4];
+    let ciphertext = &instances[0][6..6 + HAND_SIZE + 2];
+    let key = shared_key(secret_key, ephemeral_key);
+    let message = decrypt(key, nonce, ciphertext).unwrap();
+    assert_eq!(message, circuit.message());
+    assert_eq!(
+        hand_commitment(circuit.context.session_id, circuit.hand, message[HAND_SIZE]),
+        instances[0][5]
+    );
+
+    // the chip decrypts to the same message
+    let decryption = DecryptionCircuit {
+        key,
+        nonce,
+        ciphertext: ciphertext.to_vec(),
+        message: message.clone(),
+    };
+    MockProver::run(k, &decryption, decryption.instances())
+        .unwrap()
+        .assert_satisfied();
+
+    // and rejects a tampered ciphertext through its tag
+    let mut tampered = decryption.clone();
+    tampered.ciphertext[0] += Fr::from(1);
+    tampered.message[0] += Fr::from(1);
+    assert!(MockProver::run(k, &tampered, tampered.instances())
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // nobody else can
+    assert_eq!(
+        decrypt(
+            shared_key(Fr::from(0xbad), ephemeral_key),
+            nonce,
+            ciphertext
+        ),
+        Err(CipherError::InvalidTag)
+    );
+
+    // the proof does not hold for a tampered ciphertext
+    let mut instances = instances;
+    instances[0][6] += Fr::from(1);
+    assert!(MockProver::run(k, &circuit, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // nor for a public key with a component of order 2
+    let order_two = Point {
+        x: Fr::from(0),
+        y: -Fr::from(1),
+    };
+    let small_order = EncryptedHandCircuit {
+        public_key: circuit.public_key.add(&order_two),
+        ..circuit
+    };
+    assert!(!small_order.public_key.is_in_subgroup());
+    assert!(MockProver::run(k, &small_order, small_order.instances())
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("encrypted hand verified");
+}
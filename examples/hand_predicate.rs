@@ -0,0 +1,64 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext,
+    hand_predicate_chip::{Attribute, Predicate},
+    hand_predicate_circuit::HandPredicateCircuit,
+};
+
+fn main() {
+    let k = 12;
+    let context = GameContext::new(Fr::from(0x5e55), 3);
+
+    // suits 0, 1, 0, 1, 2 and ranks 0, 0, 5, 5, 4
+    let hand = [0, 13, 5, 18, 30];
+    let no_spades = HandPredicateCircuit::<Fr, 5> {
+        hand,
+        hand_salt: Fr::from(0x5a17),
+        attribute: Attribute::Suit,
+        predicate: Predicate::HasNone,
+        value: 3,
+        context,
+    };
+    let two_of_a_rank = HandPredicateCircuit {
+        attribute: Attribute::Rank,
+        predicate: Predicate::Count(2),
+        value: 5,
+        ..no_spades.clone()
+    };
+
+    for circuit in [&no_spades, &two_of_a_rank] {
+        let instances = circuit.instances();
+        MockProver::run(k, circuit, instances.clone())
+            .unwrap()
+            .assert_satisfied();
+        // the proof does not verify for another move
+        let mut replayed = instances.clone();
+        *replayed[0].last_mut().unwrap() = context.next().digest();
+        assert!(MockProver::run(k, circuit, replayed)
+            .unwrap()
+            .verify()
+            .is_err());
+    }
+    // both proofs are about the same committed hand
+    assert_eq!(no_spades.instances()[0][0], two_of_a_rank.instances()[0][0]);
+
+    // the player does hold cards of suit 0
+    let mut instances = no_spades.instances();
+    instances[0][1] = Fr::from(0);
+    assert!(MockProver::run(k, &no_spades, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // and does not hold three cards of rank 5
+    let mut instances = two_of_a_rank.instances();
+    instances[0][2] = Fr::from(3);
+    assert!(MockProver::run(k, &two_of_a_rank, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("hand predicates verified");
+}
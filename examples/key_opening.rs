@@ -0,0 +1,59 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext,
+    distinct_single_key::{decrypt_deck, DistinctSingleKeyCircuit},
+    key_opening_circuit::KeyOpeningCircuit,
+    utils::randomize,
+};
+
+fn main() {
+    let k = 9;
+    let context = GameContext::new(Fr::from(0x5e55), 0);
+
+    let raw_cards = randomize(std::array::from_fn(|i| i as u64), 20);
+    let dsk = DistinctSingleKeyCircuit::<Fr, 4, 1, 31> {
+        raw_cards,
+        key: 3,
+        key_salt: Fr::from(0x1234),
+        context,
+    };
+    let dsk_instances = dsk.instances();
+    MockProver::run(k, &dsk, dsk_instances.clone())
+        .unwrap()
+        .assert_satisfied();
+
+    // after the hand ends the key is opened against the DSK commitment
+    let opening = KeyOpeningCircuit::<Fr, 4> {
+        key: dsk.key,
+        key_salt: dsk.key_salt,
+        context: context.next(),
+    };
+    let opening_instances = opening.instances();
+    MockProver::run(k, &opening, opening_instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    let key_commitment = dsk_instances[0][dsk_instances[0].len() - 2];
+    assert_eq!(key_commitment, opening_instances[0][0]);
+    // the proof does not verify for another move
+    let mut replayed = opening_instances.clone();
+    *replayed[0].last_mut().unwrap() = context.advance(2).digest();
+    assert!(MockProver::run(k, &opening, replayed)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // the commitment is bound to the session, it does not open in another one
+    let replayed = KeyOpeningCircuit::<Fr, 4> {
+        context: GameContext::new(Fr::from(0x0712), 1),
+        ..opening.clone()
+    };
+    assert_ne!(replayed.instances()[0][0], key_commitment);
+
+    let slots = &dsk_instances[0][..dsk_instances[0].len() - 2];
+    let deck = decrypt_deck::<Fr, 4, 1, 31>(slots, opening.key);
+    assert_eq!(deck, raw_cards);
+    println!("audited deck: {deck:?}");
+}
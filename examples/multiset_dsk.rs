@@ -0,0 +1,52 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext, distinct_single_key::decrypt_deck, multiset_chip::Multiplicities,
+    multiset_dsk_circuit::MultisetDSKCircuit, utils::randomize,
+};
+
+fn main() {
+    let k = 10;
+    let context = GameContext::new(Fr::from(0x5e55), 0);
+
+    // two copies of four cards
+    let multiplicities = Multiplicities::uniform(4, 2);
+    let raw_cards = randomize(std::array::from_fn(|i| i as u64 / 2), 20);
+    let circuit = MultisetDSKCircuit::<Fr, 8, 1, 31> {
+        raw_cards,
+        multiplicities: multiplicities.clone(),
+        key: 5,
+        key_salt: Fr::from(0x1234),
+        context,
+    };
+    let instances = circuit.instances();
+    MockProver::run(k, &circuit, instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    // the proof does not verify for another move
+    let mut replayed = instances.clone();
+    *replayed[0].last_mut().unwrap() = context.next().digest();
+    assert!(MockProver::run(k, &circuit, replayed)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // the tags decrypt like a DSK deck and map back to the cards
+    let slots = &instances[0][..instances[0].len() - 2];
+    let cards = decrypt_deck::<Fr, 8, 1, 31>(slots, circuit.key)
+        .into_iter()
+        .map(|tag| multiplicities.card(tag).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(cards, raw_cards);
+
+    // a tampered deck does not verify
+    let mut instances = instances;
+    instances[0][0] += Fr::from(1);
+    assert!(MockProver::run(k, &circuit, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("multiset deck: {cards:?}");
+}
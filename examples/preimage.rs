@@ -1,11 +1,13 @@
 use halo2_utils::halo2_proofs::halo2curves::bn256::Fr;
+use zk_card::context::GameContext;
 
 fn main() {
-    let k = 7;
+    let k = 8;
 
     let circuit = zk_card::preimage_circuit::PreimageCircuit::<Fr, 5> {
         a: Fr::from(3),
         b: Fr::from(4),
+        context: GameContext::new(Fr::from(0x5e55), 0),
     };
 
     // halo2_utils::info_printer::print(k, &circuit).unwrap();
@@ -0,0 +1,71 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext,
+    distinct_single_key::{decrypt_deck, unpack_cards, DistinctSingleKeyCircuit},
+    rekey_circuit::RekeyCircuit,
+    utils::randomize,
+};
+
+fn main() {
+    let k = 10;
+    let context = GameContext::new(Fr::from(0x5e55), 0);
+
+    let raw_cards = randomize(std::array::from_fn(|i| i as u64), 20);
+    let dsk = DistinctSingleKeyCircuit::<Fr, 8, 1, 31> {
+        raw_cards,
+        key: 3,
+        key_salt: Fr::from(0x1234),
+        context,
+    };
+    let dsk_instances = dsk.instances();
+    MockProver::run(k, &dsk, dsk_instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    let slots = &dsk_instances[0][..dsk_instances[0].len() - 2];
+
+    // the key holder rotates the key without revealing either key
+    let rekey = RekeyCircuit::<Fr, 8, 1, 31> {
+        encrypted_cards: unpack_cards::<Fr, 1, 31>(slots, 8).try_into().unwrap(),
+        old_key: dsk.key,
+        old_key_salt: dsk.key_salt,
+        new_key: 6,
+        new_key_salt: Fr::from(0x5678),
+        context: context.next(),
+    };
+    let instances = rekey.instances();
+    MockProver::run(k, &rekey, instances.clone())
+        .unwrap()
+        .assert_satisfied();
+    // the proof does not verify for another move
+    let mut replayed = instances.clone();
+    *replayed[0].last_mut().unwrap() = context.advance(2).digest();
+    assert!(MockProver::run(k, &rekey, replayed)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    let num_slots = slots.len();
+    assert_eq!(&instances[0][..num_slots], slots);
+    // the old key commitment is the one of the DSK proof
+    assert_eq!(
+        instances[0][2 * num_slots],
+        dsk_instances[0][dsk_instances[0].len() - 2]
+    );
+    let new_slots = &instances[0][num_slots..2 * num_slots];
+    assert_eq!(
+        decrypt_deck::<Fr, 8, 1, 31>(new_slots, rekey.new_key),
+        raw_cards
+    );
+
+    // a different deck under the new key does not verify
+    let mut instances = instances;
+    instances[0][num_slots] += Fr::from(1);
+    assert!(MockProver::run(k, &rekey, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("rekeyed deck verified");
+}
@@ -0,0 +1,66 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext, hand_chip::hand_commitment, reshuffle_circuit::ReshuffleCircuit,
+    utils::randomize,
+};
+
+fn main() {
+    let k = 10;
+    let context = GameContext::new(Fr::from(0x5e55), 5);
+
+    let deck = randomize(std::array::from_fn(|i| i as u64), 20);
+    let first = ReshuffleCircuit::<Fr, 8> {
+        deck,
+        deck_salt: Fr::from(0xdec),
+        permutation: [3, 0, 7, 5, 1, 6, 2, 4],
+        reshuffled_salt: Fr::from(0xdec1),
+        context,
+    };
+    let second = ReshuffleCircuit::<Fr, 8> {
+        deck: first.reshuffled(),
+        deck_salt: first.reshuffled_salt,
+        permutation: [7, 6, 5, 4, 3, 2, 1, 0],
+        reshuffled_salt: Fr::from(0xdec2),
+        context: context.next(),
+    };
+
+    for circuit in [&first, &second] {
+        let instances = circuit.instances();
+        MockProver::run(k, circuit, instances.clone())
+            .unwrap()
+            .assert_satisfied();
+        // the proof does not verify for another move
+        let mut replayed = instances.clone();
+        *replayed[0].last_mut().unwrap() = circuit.context.next().digest();
+        assert!(MockProver::run(k, circuit, replayed)
+            .unwrap()
+            .verify()
+            .is_err());
+    }
+    // the second reshuffle starts from the deck committed by the first one
+    assert_eq!(first.instances()[0][1], second.instances()[0][0]);
+
+    // a deck that swapped a card for another one is not a reshuffle
+    let mut swapped = first.reshuffled();
+    swapped[0] = swapped[1];
+    let mut instances = first.instances();
+    instances[0][1] = hand_commitment(context.session_id, swapped, first.reshuffled_salt);
+    assert!(MockProver::run(k, &first, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // and no witness proves a deck that duplicates a card
+    let duplicated = ReshuffleCircuit {
+        permutation: [3, 3, 7, 5, 1, 6, 2, 4],
+        ..first.clone()
+    };
+    assert!(MockProver::run(k, &duplicated, duplicated.instances())
+        .unwrap()
+        .verify()
+        .is_err());
+    println!("reshuffled deck: {:?}", second.reshuffled());
+}
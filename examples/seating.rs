@@ -0,0 +1,64 @@
+use halo2_utils::{
+    halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr},
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext,
+    seating::{members_tree, Identity, Seating, SeatingError},
+    seating_circuit::SeatingCircuit,
+};
+
+fn main() {
+    let k = 10;
+    let context = GameContext::new(Fr::from(0x5e55), 0);
+
+    let identities = (0..4)
+        .map(|i| Identity {
+            nullifier: Fr::from(0x1d00 + i),
+            trapdoor: Fr::from(0x7d00 + i),
+        })
+        .collect::<Vec<_>>();
+    let tree = members_tree(&identities, 2);
+    let table_id = Fr::from(0x7ab1e);
+    let mut seating = Seating::new(tree.root(), table_id, context);
+
+    let seat = |position: usize, seat: u64| {
+        let proof = tree.proof(position);
+        SeatingCircuit::<Fr, 2> {
+            identity: identities[position],
+            position,
+            siblings: proof.siblings.try_into().unwrap(),
+            table_id,
+            seat,
+            context,
+        }
+    };
+
+    for (position, number) in [(1, 0), (3, 1)] {
+        let circuit = seat(position, number);
+        let instances = circuit.instances();
+        MockProver::run(k, &circuit, instances.clone())
+            .unwrap()
+            .assert_satisfied();
+        assert_eq!(instances[0], seating.instances(number, instances[0][3]));
+        seating.take_seat(number, instances[0][3]).unwrap();
+    }
+
+    // a player gets the same nullifier for another seat at the table
+    let again = seat(1, 2).instances();
+    assert_eq!(
+        seating.take_seat(2, again[0][3]),
+        Err(SeatingError::AlreadySeated)
+    );
+
+    // and a proof for one seat does not take another one
+    let circuit = seat(0, 2);
+    let nullifier = circuit.instances()[0][3];
+    assert!(
+        MockProver::run(k, &circuit, vec![seating.instances(1, nullifier)])
+            .unwrap()
+            .verify()
+            .is_err()
+    );
+    println!("seats: {:?}", seating.seats().keys().collect::<Vec<_>>());
+}
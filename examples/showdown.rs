@@ -0,0 +1,174 @@
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        halo2curves::bn256::Fr,
+        plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+    },
+    CircuitExt,
+};
+use zk_card::{
+    card::{DeckSpec, NUM_RANKS, NUM_SUITS},
+    card_chip::CardConfig,
+    compare_chip::CompareChip,
+    context::GameContext,
+    gate_chip::GateChip,
+    poker::{score_five, HandCategory},
+    poker_chip::PokerConfig,
+    showdown_circuit::ShowdownCircuit,
+};
+
+/// Scores five cards with `PokerChip::score_sorted` from the given sorted ranks,
+/// instances are `[score]`.
+#[derive(Clone)]
+struct SortedRanksCircuit {
+    cards: [u64; 5],
+    sorted: [u64; 5],
+}
+
+impl Circuit<Fr> for SortedRanksCircuit {
+    type Config = (
+        GateChip<Fr>,
+        CardConfig<Fr, NUM_SUITS, NUM_RANKS>,
+        PokerConfig<Fr>,
+        Column<Instance>,
+    );
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let card_config = CardConfig::configure(meta, Some(advice));
+        let poker_config = PokerConfig::configure(meta, Some(advice));
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        (gate_chip, card_config, poker_config, instance)
+    }
+
+    fn synthesize(
+        &self,
+        (gate_chip, card_config, poker_config, instance): Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let card_chip =
+            card_config.construct(layouter.namespace(|| "card chip"), gate_chip.clone())?;
+        let compare_chip = CompareChip::from(gate_chip.clone(), card_chip.rank_range());
+        let poker_chip = poker_config.construct(
+            layouter.namespace(|| "poker chip"),
+            gate_chip.clone(),
+            compare_chip,
+        )?;
+
+        let mut cards = vec![];
+        for card in self.cards {
+            let index = gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(Fr::from(card)),
+            )?;
+            cards.push(card_chip.decompose(layouter.namespace(|| "decompose"), index)?);
+        }
+        let mut sorted = vec![];
+        for rank in self.sorted {
+            sorted.push(gate_chip.load_private(
+                layouter.namespace(|| "load sorted rank"),
+                Value::known(Fr::from(rank)),
+            )?);
+        }
+        let score = poker_chip.score_sorted(
+            layouter.namespace(|| "score"),
+            &cards.try_into().unwrap(),
+            &sorted.try_into().unwrap(),
+        )?;
+
+        layouter.constrain_instance(score.cell(), instance, 0)
+    }
+}
+
+impl CircuitExt<Fr> for SortedRanksCircuit {
+    fn instances(&self) -> Vec<Vec<Fr>> {
+        let cards = self
+            .cards
+            .map(|index| DeckSpec::STANDARD.card(index).unwrap());
+        vec![vec![Fr::from(score_five(cards))]]
+    }
+}
+
+fn main() {
+    let k = 17;
+    let context = GameContext::new(Fr::from(0x5e55), 7);
+
+    // the board shares three suited cards with the first player's hole cards
+    let board = [8, 9, 10, 13, 26];
+    let first = ShowdownCircuit {
+        hole_cards: [11, 12],
+        hand_salt: Fr::from(0x5a17),
+        board,
+        context,
+    };
+    let second = ShowdownCircuit {
+        hole_cards: [21, 34],
+        hand_salt: Fr::from(0x5a18),
+        board,
+        context,
+    };
+
+    let mut scores = vec![];
+    for circuit in [&first, &second] {
+        let instances = circuit.instances();
+        MockProver::run(k, circuit, instances.clone())
+            .unwrap()
+            .assert_satisfied();
+        // the proof does not verify for another move
+        let mut replayed = instances.clone();
+        *replayed[0].last_mut().unwrap() = context.next().digest();
+        assert!(MockProver::run(k, circuit, replayed)
+            .unwrap()
+            .verify()
+            .is_err());
+        scores.push(instances[0][1 + board.len()]);
+    }
+    assert_eq!(
+        HandCategory::from_score(first.score()),
+        HandCategory::StraightFlush
+    );
+    assert!(first.score() > second.score());
+    assert_eq!(scores[0], Fr::from(first.score()));
+
+    // a player cannot claim a better score
+    let mut instances = second.instances();
+    instances[0][1 + board.len()] = Fr::from(first.score());
+    assert!(MockProver::run(k, &second, instances)
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // nor score a straight flush from ranks out of order
+    let honest = SortedRanksCircuit {
+        cards: [8, 9, 10, 11, 12],
+        sorted: [12, 11, 10, 9, 8],
+    };
+    MockProver::run(k, &honest, honest.instances())
+        .unwrap()
+        .assert_satisfied();
+    let unsorted = SortedRanksCircuit {
+        sorted: [12, 11, 10, 8, 9],
+        ..honest
+    };
+    assert!(MockProver::run(k, &unsorted, unsorted.instances())
+        .unwrap()
+        .verify()
+        .is_err());
+    println!(
+        "showdown: {:?} beats {:?}",
+        HandCategory::from_score(first.score()),
+        HandCategory::from_score(second.score())
+    );
+}
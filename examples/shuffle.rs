@@ -0,0 +1,44 @@
+use halo2_utils::halo2_proofs::halo2curves::bn256::Fr;
+use zk_card::{
+    context::GameContext,
+    shuffle_protocol::{Player, ShuffleProtocol, TranscriptError},
+    utils::randomize,
+};
+
+const NUM_CARDS: usize = 4;
+
+fn main() {
+    let k = 9;
+    let protocol = ShuffleProtocol::<NUM_CARDS, 1, 31>::setup(k);
+
+    let players = (0..3)
+        .map(|i| Player::<NUM_CARDS> {
+            permutation: randomize(std::array::from_fn(|i| i as u64), 10 + i).map(|i| i as usize),
+            key: i as u64 + 1,
+            key_salt: Fr::from(0x1234 + i as u64),
+        })
+        .collect::<Vec<_>>();
+
+    let context = GameContext::new(Fr::from(0x5e55), 0);
+    let transcript = protocol.shuffle(&players, context);
+    protocol.verify(&transcript).unwrap();
+
+    // the proofs do not verify for another session
+    let mut replayed = transcript.clone();
+    replayed.context = GameContext::new(Fr::from(0x5e56), 0);
+    assert_eq!(
+        protocol.verify(&replayed),
+        Err(TranscriptError::WrongContext { step: 0 })
+    );
+    println!("verified {} shuffle steps", transcript.steps.len());
+
+    // the initial deck is the identity so the opened deck is the composed permutation
+    let keys = players.iter().map(|player| player.key).collect::<Vec<_>>();
+    let deck = ShuffleProtocol::<NUM_CARDS, 1, 31>::open(&transcript, &keys);
+    let expected = players.iter().fold(
+        ShuffleProtocol::<NUM_CARDS, 1, 31>::initial_deck(),
+        |deck, player| player.permutation.map(|i| deck[i]),
+    );
+    assert_eq!(deck, expected);
+    println!("shuffled deck: {deck:?}");
+}
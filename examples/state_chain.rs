@@ -0,0 +1,84 @@
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        dev::MockProver,
+        halo2curves::bn256::Fr,
+        plonk::Error,
+    },
+    CircuitExt,
+};
+use zk_card::{
+    context::GameContext,
+    game_step::{check_chain, state_hash, GameStep},
+    gate_chip::GateChip,
+    state_transition_circuit::StateTransitionCircuit,
+};
+
+/// Players alternately add 1, 2 or 3 to a running total, the state is `[total, turn]`.
+#[derive(Clone)]
+struct RaceTo21;
+
+impl GameStep<Fr, 2> for RaceTo21 {
+    fn step(&self, [total, turn]: [Fr; 2], action: Fr) -> [Fr; 2] {
+        [total + action, Fr::from(1) - turn]
+    }
+
+    fn synthesize(
+        &self,
+        gate_chip: &GateChip<Fr>,
+        mut layouter: impl Layouter<Fr>,
+        [total, turn]: [AssignedCell<Fr, Fr>; 2],
+        action: AssignedCell<Fr, Fr>,
+    ) -> Result<[AssignedCell<Fr, Fr>; 2], Error> {
+        // (action - 1)(action - 2)(action - 3) == 0
+        let mut product =
+            gate_chip.load_constant(layouter.namespace(|| "load one"), Fr::from(1))?;
+        for i in 1..=3 {
+            let value = gate_chip.load_constant(layouter.namespace(|| "load i"), Fr::from(i))?;
+            let diff = gate_chip.sub(layouter.namespace(|| "action - i"), action.clone(), value)?;
+            product = gate_chip.mul(layouter.namespace(|| "product"), product, diff)?;
+        }
+        gate_chip.assert_constant(layouter.namespace(|| "valid action"), product, Fr::from(0))?;
+
+        let one = gate_chip.load_constant(layouter.namespace(|| "load one"), Fr::from(1))?;
+        Ok([
+            gate_chip.add(layouter.namespace(|| "total"), total, action)?,
+            gate_chip.sub(layouter.namespace(|| "turn"), one, turn)?,
+        ])
+    }
+}
+
+fn main() {
+    let k = 10;
+
+    let mut state = [Fr::from(0), Fr::from(0)];
+    let mut salt = Fr::from(0x5a17);
+    let context = GameContext::new(Fr::from(0x5e55), 0);
+    let initial_hash = state_hash(context.session_id, state, salt);
+
+    let mut transitions = vec![];
+    for (i, action) in [3, 1, 2, 3, 3, 1].into_iter().enumerate() {
+        let circuit = StateTransitionCircuit {
+            game: RaceTo21,
+            state,
+            state_salt: salt,
+            action: Fr::from(action),
+            new_state_salt: Fr::from(0x5a18 + i as u64),
+            context: context.advance(i as u64),
+        };
+        MockProver::run(k, &circuit, circuit.instances())
+            .unwrap()
+            .assert_satisfied();
+        transitions.push(circuit.instances()[0].clone());
+        state = circuit.new_state();
+        salt = circuit.new_state_salt;
+    }
+
+    let final_hash = check_chain(initial_hash, context, &transitions).unwrap();
+    assert_eq!(final_hash, state_hash(context.session_id, state, salt));
+    println!(
+        "verified {} transitions, total {:?}",
+        transitions.len(),
+        state[0]
+    );
+}
@@ -37,23 +37,26 @@ impl<F: FieldExt, const N: usize> AddModChip<F, N> {
         // add them
         let sum = self.gate_chip.add(layouter.namespace(|| "sum"), a, b)?;
 
-        // find if they overflow or not
+        // find if they overflow or not, both inputs are below N so the sum wraps at most once
         let n = F::from(N as u64);
         let w = sum
             .value()
             .copied()
-            .map(|sum| if sum >= n { F::ONE.neg() } else { F::ZERO });
+            .map(|sum| if sum >= n { F::ONE } else { F::ZERO });
         let w = self
             .gate_chip
             .load_private(layouter.namespace(|| "load w"), w)?;
+        self.gate_chip
+            .assert_bool(layouter.namespace(|| "w is a bit"), w.clone())?;
         let n = self
             .gate_chip
             .load_constant(layouter.namespace(|| "load c"), n)?;
 
-        // find the add mod value
+        // find the add mod value, sum - w * N
+        let overflow = self.gate_chip.mul(layouter.namespace(|| "w * N"), w, n)?;
         let addmod = self
             .gate_chip
-            .addmul(layouter.namespace(|| "add mul"), w, n, sum)?;
+            .sub(layouter.namespace(|| "sum - w * N"), sum, overflow)?;
 
         // ensure it is within range
         self.range_chip
@@ -0,0 +1,129 @@
+use crate::utils::fe_to_bits;
+use halo2_utils::FieldExt;
+
+/// Twisted Edwards coefficients of BabyJubJub, `a x^2 + y^2 = 1 + d x^2 y^2` over the
+/// BN254 scalar field.
+pub const A: u64 = 168700;
+pub const D: u64 = 168696;
+
+/// Order of the prime subgroup generated by [`Point::generator`].
+pub const SUBORDER: &str =
+    "2736030358979909402780800718157159386076813972158567259200215660948447373041";
+
+/// Inverse of 8 modulo [`SUBORDER`].
+const EIGHTH: &str = "2394026564107420727433200628387514462817212225638746351800188703329891451411";
+
+const BASE8_X: &str =
+    "5299619240641551281634865583518297030282874472190772894086521144482721001553";
+const BASE8_Y: &str =
+    "16950150798460657717958625567821834550301663161624707787222815936182638968203";
+
+/// A BabyJubJub point in affine coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point<F: FieldExt> {
+    pub x: F,
+    pub y: F,
+}
+
+impl<F: FieldExt> Point<F> {
+    pub fn identity() -> Self {
+        Self {
+            x: F::ZERO,
+            y: F::ONE,
+        }
+    }
+
+    /// The "Base8" generator of the prime subgroup used by circomlib.
+    pub fn generator() -> Self {
+        Self {
+            x: F::from_str_vartime(BASE8_X).unwrap(),
+            y: F::from_str_vartime(BASE8_Y).unwrap(),
+        }
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        let x2 = self.x.square();
+        let y2 = self.y.square();
+        F::from(A) * x2 + y2 == F::ONE + F::from(D) * x2 * y2
+    }
+
+    /// Complete twisted Edwards addition.
+    pub fn add(&self, other: &Self) -> Self {
+        let t = F::from(D) * self.x * other.x * self.y * other.y;
+        let x = (self.x * other.y + self.y * other.x) * (F::ONE + t).invert().unwrap();
+        let y = (self.y * other.y - F::from(A) * self.x * other.x) * (F::ONE - t).invert().unwrap();
+        Self { x, y }
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    /// Whether the point is on the curve and in the prime subgroup.
+    pub fn is_in_subgroup(&self) -> bool {
+        self.is_on_curve() && self.mul(F::from_str_vartime(SUBORDER).unwrap()) == Self::identity()
+    }
+
+    /// The point `Q` of the prime subgroup with `8 Q == self`, only defined for points of
+    /// the subgroup.
+    pub fn eighth(&self) -> Self {
+        self.mul(F::from_str_vartime(EIGHTH).unwrap())
+    }
+
+    /// Double-and-add over the little-endian bits of `scalar`.
+    pub fn mul(&self, scalar: F) -> Self {
+        fe_to_bits(scalar)
+            .into_iter()
+            .rev()
+            .fold(Self::identity(), |acc, bit| {
+                let acc = acc.add(&acc);
+                if bit {
+                    acc.add(self)
+                } else {
+                    acc
+                }
+            })
+    }
+}
+
+/// `a < b` comparing the canonical representations.
+fn repr_lt<F: FieldExt>(a: F, b: F) -> bool {
+    let (a, b) = (a.to_repr(), b.to_repr());
+    a.as_ref().iter().rev().lt(b.as_ref().iter().rev())
+}
+
+/// `a + b` modulo [`SUBORDER`] for reduced `a` and `b`. The sum cannot wrap the field
+/// since the subgroup order is below half the field modulus.
+pub fn scalar_add<F: FieldExt>(a: F, b: F) -> F {
+    let suborder = F::from_str_vartime(SUBORDER).unwrap();
+    let sum = a + b;
+    if repr_lt(sum, suborder) {
+        sum
+    } else {
+        sum - suborder
+    }
+}
+
+/// `a * b` modulo [`SUBORDER`] for a reduced `a`, by double-and-add over the bits of `b`.
+pub fn scalar_mul<F: FieldExt>(a: F, b: F) -> F {
+    fe_to_bits(b).into_iter().rev().fold(F::ZERO, |acc, bit| {
+        let acc = scalar_add(acc, acc);
+        if bit {
+            scalar_add(acc, a)
+        } else {
+            acc
+        }
+    })
+}
+
+/// Reduces any field element modulo [`SUBORDER`].
+pub fn reduce_scalar<F: FieldExt>(value: F) -> F {
+    scalar_mul(F::ONE, value)
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    babyjubjub::Point,
+    key_registration_circuit::registration_tag,
+    poseidon_chip::{poseidon_tagged, JointKeyCommit},
+};
+use halo2_utils::FieldExt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPair<F: FieldExt> {
+    pub secret_key: F,
+    pub public_key: Point<F>,
+}
+
+impl<F: FieldExt> KeyPair<F> {
+    pub fn from(secret_key: F) -> Self {
+        Self {
+            secret_key,
+            public_key: Point::generator().mul(secret_key),
+        }
+    }
+}
+
+/// The joint key cards are masked under, only all players together can unmask.
+pub fn aggregate_key<F: FieldExt>(public_keys: &[Point<F>]) -> Point<F> {
+    public_keys
+        .iter()
+        .fold(Point::identity(), |acc, key| acc.add(key))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistrationError {
+    SeatTaken,
+    /// The key is not in the prime subgroup or is the identity.
+    InvalidKey,
+    /// The registration tag does not match the table, seat and key.
+    InvalidTag,
+}
+
+/// Builds a table's joint key from key shares registered with `KeyRegistrationCircuit`,
+/// the caller verifies every proof against `[public_key.x, public_key.y, tag,
+/// context_digest]`.
+#[derive(Debug, Clone)]
+pub struct KeyAggregator<F: FieldExt> {
+    pub table_id: F,
+    keys: BTreeMap<u64, Point<F>>,
+}
+
+impl<F: FieldExt> KeyAggregator<F> {
+    pub fn new(table_id: F) -> Self {
+        Self {
+            table_id,
+            keys: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        seat: u64,
+        public_key: Point<F>,
+        tag: F,
+    ) -> Result<(), RegistrationError> {
+        if self.keys.contains_key(&seat) {
+            return Err(RegistrationError::SeatTaken);
+        }
+        // a small order component would survive the unmask shares and leak the mask mod 8
+        if !public_key.is_in_subgroup() || public_key == Point::identity() {
+            return Err(RegistrationError::InvalidKey);
+        }
+        if tag != registration_tag(self.table_id, seat, public_key) {
+            return Err(RegistrationError::InvalidTag);
+        }
+        self.keys.insert(seat, public_key);
+        Ok(())
+    }
+
+    pub fn public_keys(&self) -> Vec<Point<F>> {
+        self.keys.values().copied().collect()
+    }
+
+    pub fn joint_key(&self) -> Point<F> {
+        aggregate_key(&self.public_keys())
+    }
+
+    /// Poseidon commitment to the joint key, `Poseidon(table_id, x, y)` in the
+    /// [`JointKeyCommit`] domain.
+    pub fn commitment(&self) -> F {
+        let joint_key = self.joint_key();
+        poseidon_tagged(JointKeyCommit, [self.table_id, joint_key.x, joint_key.y])
+    }
+}
+
+/// Card `index` is encoded as `(index + 1) * G` so that no card is the identity.
+pub fn encode_card<F: FieldExt>(index: u64) -> Point<F> {
+    Point::generator().mul(F::from(index + 1))
+}
+
+/// Inverse of [`encode_card`] for a deck of `deck_size` cards.
+pub fn decode_card<F: FieldExt>(point: Point<F>, deck_size: u64) -> Option<u64> {
+    (0..deck_size).find(|index| encode_card::<F>(*index) == point)
+}
+
+/// Barnett–Smart masked card, the ElGamal ciphertext `(r G, M + r PK)` under the
+/// aggregate key. Any player can remask it and every player contributes a share to
+/// unmask it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ciphertext<F: FieldExt> {
+    pub c1: Point<F>,
+    pub c2: Point<F>,
+}
+
+impl<F: FieldExt> Ciphertext<F> {
+    pub fn mask(card: Point<F>, public_key: Point<F>, randomness: F) -> Self {
+        Self {
+            c1: Point::generator().mul(randomness),
+            c2: card.add(&public_key.mul(randomness)),
+        }
+    }
+
+    /// Re-randomizes the ciphertext without changing the card.
+    pub fn remask(&self, public_key: Point<F>, randomness: F) -> Self {
+        Self {
+            c1: self.c1.add(&Point::generator().mul(randomness)),
+            c2: self.c2.add(&public_key.mul(randomness)),
+        }
+    }
+
+    /// A player's unmasking share `sk * c1`.
+    pub fn unmask_share(&self, secret_key: F) -> Point<F> {
+        self.c1.mul(secret_key)
+    }
+
+    /// Removes the shares of every player from `c2`, which leaves the card point.
+    pub fn unmask(&self, shares: &[Point<F>]) -> Point<F> {
+        shares.iter().fold(self.c2, |acc, share| acc.sub(share))
+    }
+}
+
+/// Collects the unmasking shares of a card, one per player. Shares are expected to be
+/// checked with `UnmaskShareCircuit` proofs before they are added.
+#[derive(Debug, Clone)]
+pub struct UnmaskShares<F: FieldExt> {
+    pub ciphertext: Ciphertext<F>,
+    shares: Vec<Option<Point<F>>>,
+}
+
+impl<F: FieldExt> UnmaskShares<F> {
+    pub fn new(ciphertext: Ciphertext<F>, num_players: usize) -> Self {
+        Self {
+            ciphertext,
+            shares: vec![None; num_players],
+        }
+    }
+
+    pub fn add_share(&mut self, player: usize, share: Point<F>) {
+        self.shares[player] = Some(share);
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.shares.iter().all(Option::is_some)
+    }
+
+    /// The card index once every player contributed a share, `None` before that or if
+    /// the unmasked point is not a card of a `deck_size` deck.
+    pub fn reveal(&self, deck_size: u64) -> Option<u64> {
+        let shares = self.shares.iter().copied().collect::<Option<Vec<_>>>()?;
+        decode_card(self.ciphertext.unmask(&shares), deck_size)
+    }
+}
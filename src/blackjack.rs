@@ -0,0 +1,43 @@
+use crate::card::Rank;
+
+pub const BLACKJACK: u64 = 21;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Bust,
+    /// A value of at most 21 that is not a natural blackjack.
+    Standing,
+    /// 21 with the first two cards.
+    Blackjack,
+}
+
+/// Value of a card with the ace counted as 1.
+pub fn card_value(rank: u64) -> u64 {
+    match rank {
+        rank if rank == Rank::Ace as u64 => 1,
+        rank if rank >= Rank::Jack as u64 => 10,
+        rank => rank + 2,
+    }
+}
+
+/// Best value of a hand, one ace counts as 11 when that does not bust the hand.
+pub fn hand_value(ranks: &[u64]) -> u64 {
+    let hard: u64 = ranks.iter().map(|rank| card_value(*rank)).sum();
+    let has_ace = ranks.iter().any(|rank| *rank == Rank::Ace as u64);
+    if has_ace && hard + 10 <= BLACKJACK {
+        hard + 10
+    } else {
+        hard
+    }
+}
+
+pub fn outcome(ranks: &[u64]) -> Outcome {
+    let value = hand_value(ranks);
+    if value > BLACKJACK {
+        Outcome::Bust
+    } else if value == BLACKJACK && ranks.len() == 2 {
+        Outcome::Blackjack
+    } else {
+        Outcome::Standing
+    }
+}
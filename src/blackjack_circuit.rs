@@ -0,0 +1,234 @@
+use crate::{
+    blackjack::{hand_value, outcome, BLACKJACK},
+    card::{DeckSpec, Rank, NUM_RANKS, NUM_SUITS},
+    card_chip::CardConfig,
+    compare_chip::CompareChip,
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    poseidon_chip::PoseidonChip,
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Ranks and hand values stay below this.
+const VALUE_RANGE: usize = 128;
+
+/// The soft value of eleven faces is 120, one more card could overflow [`VALUE_RANGE`].
+const MAX_HAND_SIZE: usize = 11;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlackjackMode {
+    /// Expose the exact hand value.
+    Value,
+    /// Expose only the outcome, 0 for bust, 1 for standing and 2 for blackjack.
+    Outcome,
+}
+
+/// Scores a committed blackjack hand of `HAND_SIZE` standard deck cards.
+///
+/// Instances are `[hand_commitment, result, context_digest]`, see [`BlackjackMode`] for
+/// the result.
+#[derive(Clone)]
+pub struct BlackjackCircuit<F: FieldExt, const HAND_SIZE: usize> {
+    pub hand: [u64; HAND_SIZE],
+    pub hand_salt: F,
+    pub mode: BlackjackMode,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> BlackjackCircuit<F, HAND_SIZE> {
+    pub fn result(&self) -> u64 {
+        let ranks = self
+            .hand
+            .map(|index| DeckSpec::STANDARD.card(index).unwrap().rank);
+        match self.mode {
+            BlackjackMode::Value => hand_value(&ranks),
+            BlackjackMode::Outcome => outcome(&ranks) as u64,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BlackjackCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    card_config: CardConfig<F, NUM_SUITS, NUM_RANKS>,
+    range_config: RangeConfig<F, VALUE_RANGE>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> Circuit<F> for BlackjackCircuit<F, HAND_SIZE> {
+    type Config = BlackjackCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        assert!(
+            HAND_SIZE <= MAX_HAND_SIZE,
+            "hand values must fit the range table"
+        );
+
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let card_config = CardConfig::configure(meta, Some(advice));
+        let range_config = RangeConfig::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        BlackjackCircuitConfig {
+            gate_chip,
+            card_config,
+            range_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let card_chip = config
+            .card_config
+            .construct(layouter.namespace(|| "card chip"), gate_chip.clone())?;
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let compare_chip = CompareChip::from(gate_chip.clone(), range_chip);
+        let hand_chip = HandChip::from(config.poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let ace = gate_chip
+            .load_constant(layouter.namespace(|| "load ace"), F::from(Rank::Ace as u64))?;
+        let ten = gate_chip
+            .load_constant(layouter.namespace(|| "load ten"), F::from(Rank::Ten as u64))?;
+        let zero = gate_chip.load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        let one = gate_chip.load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        let two = gate_chip.load_constant(layouter.namespace(|| "load two"), F::from(2))?;
+        let ten_points = gate_chip.load_constant(layouter.namespace(|| "load 10"), F::from(10))?;
+
+        let mut hand = vec![];
+        let mut hard = zero.clone();
+        let mut aces = zero.clone();
+        for index in self.hand {
+            let index = gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(F::from(index)),
+            )?;
+            let card = card_chip.decompose(layouter.namespace(|| "decompose"), index.clone())?;
+            hand.push(index);
+
+            // two to ten are worth rank + 2, faces 10 and the ace 1
+            let is_ace = gate_chip.is_equal(
+                layouter.namespace(|| "is ace"),
+                card.rank.clone(),
+                ace.clone(),
+            )?;
+            let is_face = compare_chip.is_lt(
+                layouter.namespace(|| "is face or ace"),
+                ten.clone(),
+                card.rank.clone(),
+            )?;
+            let pips = gate_chip.add(layouter.namespace(|| "rank + 2"), card.rank, two.clone())?;
+            let face_value = gate_chip.select(
+                layouter.namespace(|| "face value"),
+                is_ace.clone(),
+                one.clone(),
+                ten_points.clone(),
+            )?;
+            let value =
+                gate_chip.select(layouter.namespace(|| "value"), is_face, face_value, pips)?;
+
+            hard = gate_chip.add(layouter.namespace(|| "hard"), hard, value)?;
+            aces = gate_chip.add(layouter.namespace(|| "aces"), aces, is_ace)?;
+        }
+
+        let hand_salt = gate_chip.load_private(
+            layouter.namespace(|| "load hand salt"),
+            Value::known(self.hand_salt),
+        )?;
+        let hand_commitment = hand_chip.commit(
+            layouter.namespace(|| "hand commitment"),
+            context.session_id.clone(),
+            hand.try_into().unwrap(),
+            hand_salt,
+        )?;
+
+        // one ace counts as 11 if the soft value does not bust
+        let no_ace = gate_chip.is_zero(layouter.namespace(|| "no ace"), aces)?;
+        let soft = gate_chip.add(
+            layouter.namespace(|| "soft"),
+            hard.clone(),
+            ten_points.clone(),
+        )?;
+        let blackjack_plus_one =
+            gate_chip.load_constant(layouter.namespace(|| "load 22"), F::from(BLACKJACK + 1))?;
+        let soft_fits = compare_chip.is_lt(
+            layouter.namespace(|| "soft fits"),
+            soft.clone(),
+            blackjack_plus_one,
+        )?;
+        let use_soft = gate_chip.select(
+            layouter.namespace(|| "use soft"),
+            no_ace,
+            zero.clone(),
+            soft_fits,
+        )?;
+        let value = gate_chip.select(layouter.namespace(|| "value"), use_soft, soft, hard)?;
+
+        let result = match self.mode {
+            BlackjackMode::Value => value,
+            BlackjackMode::Outcome => {
+                let blackjack = gate_chip
+                    .load_constant(layouter.namespace(|| "load 21"), F::from(BLACKJACK))?;
+                let bust = compare_chip.is_lt(
+                    layouter.namespace(|| "bust"),
+                    blackjack.clone(),
+                    value.clone(),
+                )?;
+                let standing = if HAND_SIZE == 2 {
+                    let natural =
+                        gate_chip.is_equal(layouter.namespace(|| "natural"), value, blackjack)?;
+                    gate_chip.add(layouter.namespace(|| "standing"), one.clone(), natural)?
+                } else {
+                    one
+                };
+                gate_chip.select(layouter.namespace(|| "outcome"), bust, zero, standing)?
+            }
+        };
+
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(hand_commitment.cell(), config.instance, 0)?;
+        layouter.constrain_instance(result.cell(), config.instance, 1)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 2)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> CircuitExt<F> for BlackjackCircuit<F, HAND_SIZE> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            hand_commitment(self.context.session_id, self.hand, self.hand_salt),
+            F::from(self.result()),
+            self.context.digest(),
+        ]]
+    }
+}
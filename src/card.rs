@@ -0,0 +1,142 @@
+/// Number of suits in a standard 52-card deck.
+pub const NUM_SUITS: usize = 4;
+/// Number of ranks per suit in a standard 52-card deck.
+pub const NUM_RANKS: usize = 13;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; NUM_SUITS] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+}
+
+/// Ranks are ordered from two up to ace, so the ace is the highest rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub const ALL: [Rank; NUM_RANKS] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+}
+
+/// A card as a `(suit, rank)` pair. Jokers live in an extra suit placed after the
+/// regular ones, so every card of a deck has the canonical index
+/// `suit * ranks + rank`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub suit: u64,
+    pub rank: u64,
+}
+
+impl Card {
+    pub fn standard(suit: Suit, rank: Rank) -> Self {
+        Self {
+            suit: suit as u64,
+            rank: rank as u64,
+        }
+    }
+
+    /// Suit and rank of a card from a standard deck, `None` for jokers and custom suits.
+    pub fn standard_parts(&self) -> Option<(Suit, Rank)> {
+        Some((
+            *Suit::ALL.get(self.suit as usize)?,
+            *Rank::ALL.get(self.rank as usize)?,
+        ))
+    }
+}
+
+/// Describes the shape of a deck: `suits` regular suits of `ranks` cards each
+/// followed by `jokers` jokers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeckSpec {
+    pub suits: u64,
+    pub ranks: u64,
+    pub jokers: u64,
+}
+
+impl DeckSpec {
+    pub const STANDARD: DeckSpec = DeckSpec::custom(NUM_SUITS as u64, NUM_RANKS as u64, 0);
+    pub const WITH_JOKERS: DeckSpec = DeckSpec::custom(NUM_SUITS as u64, NUM_RANKS as u64, 2);
+
+    pub const fn custom(suits: u64, ranks: u64, jokers: u64) -> Self {
+        assert!(jokers <= ranks, "jokers must fit in a single suit");
+        Self {
+            suits,
+            ranks,
+            jokers,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.suits * self.ranks + self.jokers
+    }
+
+    /// The suit value jokers decompose into.
+    pub fn joker_suit(&self) -> u64 {
+        self.suits
+    }
+
+    pub fn joker(&self, n: u64) -> Option<Card> {
+        (n < self.jokers).then_some(Card {
+            suit: self.joker_suit(),
+            rank: n,
+        })
+    }
+
+    pub fn is_joker(&self, card: Card) -> bool {
+        card.suit == self.joker_suit()
+    }
+
+    pub fn index(&self, card: Card) -> Option<u64> {
+        let valid = if self.is_joker(card) {
+            card.rank < self.jokers
+        } else {
+            card.suit < self.suits && card.rank < self.ranks
+        };
+        valid.then_some(card.suit * self.ranks + card.rank)
+    }
+
+    pub fn card(&self, index: u64) -> Option<Card> {
+        (index < self.size()).then_some(Card {
+            suit: index / self.ranks,
+            rank: index % self.ranks,
+        })
+    }
+
+    /// All cards of the deck ordered by their index.
+    pub fn cards(&self) -> impl Iterator<Item = Card> + '_ {
+        (0..self.size()).map(|index| self.card(index).unwrap())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    gate_chip::GateChip,
+    range_chip::{RangeChip, RangeConfig},
+    utils::fe_to_u64,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::{Advice, Column, Error},
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// A card index cell together with its range checked suit and rank cells.
+#[derive(Clone, Debug)]
+pub struct AssignedCard<F: FieldExt> {
+    pub index: Cell<F>,
+    pub suit: Cell<F>,
+    pub rank: Cell<F>,
+}
+
+#[derive(Clone)]
+pub struct CardConfig<F: FieldExt, const SUITS: usize, const RANKS: usize> {
+    suit_range: RangeConfig<F, SUITS>,
+    rank_range: RangeConfig<F, RANKS>,
+}
+
+impl<F: FieldExt, const SUITS: usize, const RANKS: usize> CardConfig<F, SUITS, RANKS> {
+    pub fn configure(
+        meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>,
+        advice: Option<Column<Advice>>,
+    ) -> Self {
+        let advice = advice.unwrap_or(meta.advice_column());
+        Self {
+            suit_range: RangeConfig::configure(meta, Some(advice)),
+            rank_range: RangeConfig::configure(meta, Some(advice)),
+        }
+    }
+
+    pub fn construct(
+        &self,
+        mut layouter: impl Layouter<F>,
+        gate_chip: GateChip<F>,
+    ) -> Result<CardChip<F, SUITS, RANKS>, Error> {
+        Ok(CardChip {
+            gate_chip,
+            suit_range: self
+                .suit_range
+                .construct(layouter.namespace(|| "suit range"))?,
+            rank_range: self
+                .rank_range
+                .construct(layouter.namespace(|| "rank range"))?,
+        })
+    }
+}
+
+/// Decomposes card indices into `(suit, rank)` following the `suit * RANKS + rank`
+/// encoding of [`crate::card::DeckSpec`]. Decks with jokers use `SUITS = suits + 1`.
+///
+/// Only the suit and rank are range checked, so when the last suit is not full
+/// (e.g. two jokers) the index itself must be range checked against the deck size
+/// by the caller.
+#[derive(Clone)]
+pub struct CardChip<F: FieldExt, const SUITS: usize, const RANKS: usize> {
+    gate_chip: GateChip<F>,
+    suit_range: RangeChip<F, SUITS>,
+    rank_range: RangeChip<F, RANKS>,
+}
+
+impl<F: FieldExt, const SUITS: usize, const RANKS: usize> CardChip<F, SUITS, RANKS> {
+    /// The rank table, so ranks can be compared without another table of the same size.
+    pub fn rank_range(&self) -> RangeChip<F, RANKS> {
+        self.rank_range.clone()
+    }
+
+    pub fn decompose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        index: Cell<F>,
+    ) -> Result<AssignedCard<F>, Error> {
+        let suit = self.gate_chip.load_private(
+            layouter.namespace(|| "load suit"),
+            index
+                .value()
+                .map(|index| F::from(fe_to_u64(*index) / RANKS as u64)),
+        )?;
+        let rank = self.gate_chip.load_private(
+            layouter.namespace(|| "load rank"),
+            index
+                .value()
+                .map(|index| F::from(fe_to_u64(*index) % RANKS as u64)),
+        )?;
+
+        self.suit_range
+            .range_constrain(layouter.namespace(|| "suit range"), suit.clone())?;
+        self.rank_range
+            .range_constrain(layouter.namespace(|| "rank range"), rank.clone())?;
+
+        let composed =
+            self.compose(layouter.namespace(|| "compose"), suit.clone(), rank.clone())?;
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "index"), composed, index.clone())?;
+
+        Ok(AssignedCard { index, suit, rank })
+    }
+
+    /// Computes `suit * RANKS + rank`, the inputs are assumed to be range checked.
+    pub fn compose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        suit: Cell<F>,
+        rank: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let ranks = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load ranks"), F::from(RANKS as u64))?;
+        self.gate_chip.addmul(
+            layouter.namespace(|| "suit * ranks + rank"),
+            suit,
+            ranks,
+            rank,
+        )
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    gate_chip::GateChip,
+    poseidon_chip::{domain_capacity, PoseidonChip, PoseidonCipher},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// In-circuit counterpart of [`crate::poseidon_cipher`].
+#[derive(Clone)]
+pub struct CipherChip<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+}
+
+impl<F: FieldExt> CipherChip<F> {
+    pub fn from(gate_chip: GateChip<F>, poseidon_chip: PoseidonChip<F, 2>) -> Self {
+        Self {
+            gate_chip,
+            poseidon_chip,
+        }
+    }
+
+    fn initial_state(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: Cell<F>,
+        nonce: Cell<F>,
+        length: usize,
+    ) -> Result<[Cell<F>; 3], Error> {
+        let capacity = self.gate_chip.load_constant(
+            layouter.namespace(|| "load capacity"),
+            domain_capacity::<F, PoseidonCipher>(length),
+        )?;
+        self.poseidon_chip
+            .permute(layouter.namespace(|| "absorb key"), [key, nonce, capacity])
+    }
+
+    /// Encrypts `message`, see [`crate::poseidon_cipher::encrypt`]. The last cell is the
+    /// authentication tag.
+    pub fn encrypt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: Cell<F>,
+        nonce: Cell<F>,
+        message: &[Cell<F>],
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let mut state = self.initial_state(
+            layouter.namespace(|| "initial state"),
+            key,
+            nonce,
+            message.len(),
+        )?;
+        let mut ciphertext = vec![];
+        for chunk in message.chunks(2) {
+            for (i, value) in chunk.iter().enumerate() {
+                state[i] = self.gate_chip.add(
+                    layouter.namespace(|| "absorb"),
+                    state[i].clone(),
+                    value.clone(),
+                )?;
+                ciphertext.push(state[i].clone());
+            }
+            state = self
+                .poseidon_chip
+                .permute(layouter.namespace(|| "permute"), state)?;
+        }
+        let [tag, _, _] = state;
+        ciphertext.push(tag);
+        Ok(ciphertext)
+    }
+
+    /// Decrypts `ciphertext` and constrains its authentication tag, see
+    /// [`crate::poseidon_cipher::decrypt`].
+    pub fn decrypt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: Cell<F>,
+        nonce: Cell<F>,
+        ciphertext: &[Cell<F>],
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let (tag, ciphertext) = ciphertext
+            .split_last()
+            .expect("the ciphertext ends with the tag");
+        let mut state = self.initial_state(
+            layouter.namespace(|| "initial state"),
+            key,
+            nonce,
+            ciphertext.len(),
+        )?;
+        let mut message = vec![];
+        for chunk in ciphertext.chunks(2) {
+            for (i, value) in chunk.iter().enumerate() {
+                message.push(self.gate_chip.sub(
+                    layouter.namespace(|| "squeeze"),
+                    value.clone(),
+                    state[i].clone(),
+                )?);
+                state[i] = value.clone();
+            }
+            state = self
+                .poseidon_chip
+                .permute(layouter.namespace(|| "permute"), state)?;
+        }
+        self.gate_chip.assert_equal(
+            layouter.namespace(|| "check tag"),
+            state[0].clone(),
+            tag.clone(),
+        )?;
+        Ok(message)
+    }
+}
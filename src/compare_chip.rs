@@ -0,0 +1,142 @@
+use crate::{gate_chip::GateChip, range_chip::RangeChip, utils::fe_to_u64};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// Comparisons built on top of `RangeChip`, the compared values are expected to be
+/// below `SIZE` unless stated otherwise.
+#[derive(Clone)]
+pub struct CompareChip<F: FieldExt, const SIZE: usize> {
+    gate_chip: GateChip<F>,
+    range_chip: RangeChip<F, SIZE>,
+}
+
+impl<F: FieldExt, const SIZE: usize> CompareChip<F, SIZE> {
+    pub fn from(gate_chip: GateChip<F>, range_chip: RangeChip<F, SIZE>) -> Self {
+        Self {
+            gate_chip,
+            range_chip,
+        }
+    }
+
+    /// Constrains `a <= b`.
+    pub fn assert_lte(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+    ) -> Result<(), Error> {
+        let diff = self.gate_chip.sub(layouter.namespace(|| "b - a"), b, a)?;
+        self.range_chip
+            .range_constrain(layouter.namespace(|| "b - a range"), diff)?;
+        Ok(())
+    }
+
+    /// Constrains `a < b`.
+    pub fn assert_lt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+    ) -> Result<(), Error> {
+        let one = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        let a = self.gate_chip.add(layouter.namespace(|| "a + 1"), a, one)?;
+        self.assert_lte(layouter.namespace(|| "a + 1 <= b"), a, b)
+    }
+
+    /// Returns a cell that is 1 if `a < b` and 0 otherwise.
+    pub fn is_lt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let lt = self.gate_chip.load_private(
+            layouter.namespace(|| "load lt"),
+            a.value().zip(b.value()).map(|(a, b)| {
+                if fe_to_u64(*a) < fe_to_u64(*b) {
+                    F::ONE
+                } else {
+                    F::ZERO
+                }
+            }),
+        )?;
+        self.gate_chip
+            .assert_bool(layouter.namespace(|| "lt bool"), lt.clone())?;
+
+        // b - a - 1 is in range when a < b, a - b is in range otherwise
+        let one = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        let a_plus_one = self
+            .gate_chip
+            .add(layouter.namespace(|| "a + 1"), a.clone(), one)?;
+        let lt_diff =
+            self.gate_chip
+                .sub(layouter.namespace(|| "b - a - 1"), b.clone(), a_plus_one)?;
+        let gte_diff = self.gate_chip.sub(layouter.namespace(|| "a - b"), a, b)?;
+        let diff =
+            self.gate_chip
+                .select(layouter.namespace(|| "diff"), lt.clone(), lt_diff, gte_diff)?;
+        self.range_chip
+            .range_constrain(layouter.namespace(|| "diff range"), diff)?;
+
+        Ok(lt)
+    }
+
+    /// Constrains `value < SIZE^limbs` by splitting it into range checked limbs. The
+    /// value must fit in a `u64`.
+    pub fn range_constrain_limbs(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Cell<F>,
+        limbs: usize,
+    ) -> Result<(), Error> {
+        let base = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load base"), F::from(SIZE as u64))?;
+
+        let mut acc = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        for i in (0..limbs).rev() {
+            let limb = self.gate_chip.load_private(
+                layouter.namespace(|| "load limb"),
+                value.value().map(|value| {
+                    F::from(fe_to_u64(*value) / (SIZE as u64).pow(i as u32) % SIZE as u64)
+                }),
+            )?;
+            self.range_chip
+                .range_constrain(layouter.namespace(|| "limb range"), limb.clone())?;
+            acc = self.gate_chip.addmul(
+                layouter.namespace(|| "acc * base + limb"),
+                acc,
+                base.clone(),
+                limb,
+            )?;
+        }
+
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "recomposed"), acc, value)
+    }
+
+    /// Constrains `a <= b` for values below `SIZE^limbs`.
+    pub fn assert_lte_limbs(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+        limbs: usize,
+    ) -> Result<(), Error> {
+        let diff = self.gate_chip.sub(layouter.namespace(|| "b - a"), b, a)?;
+        self.range_constrain_limbs(layouter.namespace(|| "b - a range"), diff, limbs)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    gate_chip::GateChip,
+    poseidon_chip::{poseidon_tagged, ContextDigest, PoseidonChip},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter, Value},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// The game session and move a proof is made for. Every game circuit exposes
+/// [`GameContext::digest`] as its last instance, so a proof cannot be replayed in another
+/// session or for another move.
+///
+/// Commitments to keys, hands, offsets, game states and deck leaves absorb the session id,
+/// so they cannot be opened in another session. They stay open across the moves of the
+/// session, so the move nonce is absorbed into the digest and into the message of a signed
+/// action instead. Circuits hash the digest from the same session id cell as their
+/// commitments, see [`ContextChip::load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameContext<F: FieldExt> {
+    pub session_id: F,
+    pub move_nonce: u64,
+}
+
+impl<F: FieldExt> GameContext<F> {
+    pub fn new(session_id: F, move_nonce: u64) -> Self {
+        Self {
+            session_id,
+            move_nonce,
+        }
+    }
+
+    /// The context `moves` moves later in the same session.
+    pub fn advance(&self, moves: u64) -> Self {
+        Self::new(self.session_id, self.move_nonce + moves)
+    }
+
+    pub fn next(&self) -> Self {
+        self.advance(1)
+    }
+
+    /// `Poseidon(session_id, move_nonce)` in the [`ContextDigest`] domain.
+    pub fn digest(&self) -> F {
+        poseidon_tagged(ContextDigest, [self.session_id, F::from(self.move_nonce)])
+    }
+
+    /// Whether the instances of a game circuit were made for this context.
+    pub fn check(&self, instances: &[F]) -> bool {
+        instances.last() == Some(&self.digest())
+    }
+}
+
+/// In-circuit [`GameContext`].
+#[derive(Clone, Debug)]
+pub struct AssignedContext<F: FieldExt> {
+    pub session_id: Cell<F>,
+    pub move_nonce: Cell<F>,
+}
+
+/// Loads the [`GameContext`] of a circuit and hashes it into its digest.
+#[derive(Clone)]
+pub struct ContextChip<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+}
+
+impl<F: FieldExt> ContextChip<F> {
+    pub fn from(gate_chip: GateChip<F>, poseidon_chip: PoseidonChip<F, 2>) -> Self {
+        Self {
+            gate_chip,
+            poseidon_chip,
+        }
+    }
+
+    pub fn load(
+        &self,
+        mut layouter: impl Layouter<F>,
+        context: &GameContext<F>,
+    ) -> Result<AssignedContext<F>, Error> {
+        Ok(AssignedContext {
+            session_id: self.gate_chip.load_private(
+                layouter.namespace(|| "load session id"),
+                Value::known(context.session_id),
+            )?,
+            move_nonce: self.gate_chip.load_private(
+                layouter.namespace(|| "load move nonce"),
+                Value::known(F::from(context.move_nonce)),
+            )?,
+        })
+    }
+
+    /// In-circuit counterpart of [`GameContext::digest`].
+    pub fn digest(
+        &self,
+        mut layouter: impl Layouter<F>,
+        context: &AssignedContext<F>,
+    ) -> Result<Cell<F>, Error> {
+        self.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "context digest"),
+            ContextDigest,
+            [context.session_id.clone(), context.move_nonce.clone()],
+        )
+    }
+}
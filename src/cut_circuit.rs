@@ -0,0 +1,177 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    deck_chip::DeckChip,
+    distinct_single_key::pack_cards,
+    gate_chip::GateChip,
+    poseidon_chip::{poseidon_tagged, KeyCommit, PoseidonChip},
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Commitment to a cut offset, it has the same form as the DSK key commitment so the
+/// offset can be revealed later in the session with `KeyOpeningCircuit`.
+pub fn offset_commitment<F: FieldExt>(session_id: F, offset: u64, salt: F) -> F {
+    poseidon_tagged(KeyCommit, [session_id, F::from(offset), salt])
+}
+
+/// Cuts a packed deck, proves that the output deck is the input deck rotated by a
+/// secret offset in `[0, NUM_CARDS)`.
+///
+/// Decks are packed like DSK, instances are `[input slots.., output slots..,
+/// offset_commitment, context_digest]`.
+#[derive(Clone)]
+pub struct CutCircuit<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+> {
+    pub deck: [u64; NUM_CARDS],
+    pub offset: u64,
+    pub offset_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    CutCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    /// The card at position `i` of the cut deck is `deck[(i + offset) % NUM_CARDS]`.
+    pub fn cut_deck(&self) -> [u64; NUM_CARDS] {
+        std::array::from_fn(|i| self.deck[(i + self.offset as usize) % NUM_CARDS])
+    }
+}
+
+#[derive(Clone)]
+pub struct CutCircuitConfig<F: FieldExt, const NUM_CARDS: usize> {
+    gate_chip: GateChip<F>,
+    range_config: RangeConfig<F, NUM_CARDS>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    Circuit<F> for CutCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    type Config = CutCircuitConfig<F, NUM_CARDS>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let range_config = RangeConfig::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        CutCircuitConfig {
+            gate_chip,
+            range_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let deck_chip =
+            DeckChip::<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>::from(gate_chip.clone(), range_chip);
+
+        let mut input_slots = vec![];
+        for slot in pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.deck) {
+            input_slots.push(
+                gate_chip
+                    .load_private(layouter.namespace(|| "load input slot"), Value::known(slot))?,
+            );
+        }
+        let deck = deck_chip.unpack_deck(layouter.namespace(|| "unpack input"), &input_slots)?;
+
+        // the one-hot flags also constrain the offset to [0, NUM_CARDS)
+        let offset = gate_chip.load_private(
+            layouter.namespace(|| "load offset"),
+            Value::known(F::from(self.offset)),
+        )?;
+        let flags = gate_chip.one_hot(
+            layouter.namespace(|| "offset flags"),
+            offset.clone(),
+            NUM_CARDS,
+        )?;
+
+        let mut cut_deck = vec![];
+        for i in 0..NUM_CARDS {
+            let rotated = (0..NUM_CARDS)
+                .map(|j| deck[(i + j) % NUM_CARDS].clone())
+                .collect::<Vec<_>>();
+            cut_deck.push(gate_chip.inner_product(
+                layouter.namespace(|| "cut card"),
+                &flags,
+                &rotated,
+            )?);
+        }
+        let output_slots = deck_chip.pack_deck(layouter.namespace(|| "pack output"), &cut_deck)?;
+
+        let offset_salt = gate_chip.load_private(
+            layouter.namespace(|| "load offset salt"),
+            Value::known(self.offset_salt),
+        )?;
+        let offset_commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "offset commitment"),
+            KeyCommit,
+            [context.session_id.clone(), offset, offset_salt],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        for (i, slot) in input_slots.iter().chain(output_slots.iter()).enumerate() {
+            layouter.constrain_instance(slot.cell(), config.instance, i)?;
+        }
+        layouter.constrain_instance(
+            offset_commitment.cell(),
+            config.instance,
+            input_slots.len() + output_slots.len(),
+        )?;
+        layouter.constrain_instance(
+            context_digest.cell(),
+            config.instance,
+            input_slots.len() + output_slots.len() + 1,
+        )?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    CircuitExt<F> for CutCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        let mut values = pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.deck);
+        values.extend(pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.cut_deck()));
+        values.push(offset_commitment(
+            self.context.session_id,
+            self.offset,
+            self.offset_salt,
+        ));
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
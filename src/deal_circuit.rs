@@ -0,0 +1,285 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    deck_chip::DeckChip,
+    distinct_single_key::{decrypt_card, key_commitment, unpack_cards},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    poseidon_chip::{KeyCommit, PoseidonChip},
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// The dealt mask is a `u64`, one bit per deck position.
+const MAX_CARDS: usize = u64::BITS as usize;
+
+/// Deals `HAND_SIZE` positions of a deck published by `DistinctSingleKeyCircuit` to a
+/// player and commits to the plaintext hand with `hand_chip::hand_commitment`.
+///
+/// Dealt positions are tracked as a bitmask (so `NUM_CARDS <= 64`), the circuit proves
+/// that none of the dealt positions was set in `dealt` and outputs the updated mask.
+/// Both masks are public, so the dealt positions are public too, only the cards stay
+/// private.
+///
+/// Instances are `[slots.., key_commitment, dealt, dealt_after, hand_commitment,
+/// context_digest]`.
+#[derive(Clone)]
+pub struct DealCircuit<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+    const HAND_SIZE: usize,
+> {
+    pub slots: Vec<F>,
+    pub key: u64,
+    pub key_salt: F,
+    pub dealt: u64,
+    pub positions: [usize; HAND_SIZE],
+    pub hand_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<
+        F: FieldExt,
+        const NUM_CARDS: usize,
+        const WORD_BYTES: usize,
+        const FIELD_BYTES: usize,
+        const HAND_SIZE: usize,
+    > DealCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES, HAND_SIZE>
+{
+    pub fn hand(&self) -> [u64; HAND_SIZE] {
+        let encrypted_cards = unpack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.slots, NUM_CARDS);
+        self.positions
+            .map(|position| decrypt_card::<NUM_CARDS>(encrypted_cards[position], self.key))
+    }
+
+    pub fn dealt_after(&self) -> u64 {
+        self.positions
+            .iter()
+            .fold(self.dealt, |dealt, position| dealt | 1 << position)
+    }
+}
+
+#[derive(Clone)]
+pub struct DealCircuitConfig<F: FieldExt, const NUM_CARDS: usize> {
+    gate_chip: GateChip<F>,
+    range_config: RangeConfig<F, NUM_CARDS>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<
+        F: FieldExt,
+        const NUM_CARDS: usize,
+        const WORD_BYTES: usize,
+        const FIELD_BYTES: usize,
+        const HAND_SIZE: usize,
+    > Circuit<F> for DealCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES, HAND_SIZE>
+{
+    type Config = DealCircuitConfig<F, NUM_CARDS>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        assert!(
+            NUM_CARDS <= MAX_CARDS,
+            "the dealt mask holds at most 64 positions"
+        );
+
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let range_config = RangeConfig::<F, NUM_CARDS>::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        DealCircuitConfig {
+            gate_chip,
+            range_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let deck_chip =
+            DeckChip::<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>::from(gate_chip.clone(), range_chip);
+        let hand_chip = HandChip::from(config.poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let mut slots = vec![];
+        for slot in self.slots.iter() {
+            slots.push(
+                gate_chip.load_private(layouter.namespace(|| "load slot"), Value::known(*slot))?,
+            );
+        }
+        let encrypted_cards =
+            deck_chip.unpack_deck(layouter.namespace(|| "unpack deck"), &slots)?;
+
+        let key = gate_chip.load_private(
+            layouter.namespace(|| "load key"),
+            Value::known(F::from(self.key)),
+        )?;
+        let key_salt = gate_chip.load_private(
+            layouter.namespace(|| "load key salt"),
+            Value::known(self.key_salt),
+        )?;
+        let key_commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "key commitment"),
+            KeyCommit,
+            [context.session_id.clone(), key.clone(), key_salt],
+        )?;
+
+        // split the dealt mask into one boolean per position
+        let dealt = gate_chip.load_private(
+            layouter.namespace(|| "load dealt"),
+            Value::known(F::from(self.dealt)),
+        )?;
+        let mut powers = vec![];
+        let mut dealt_bits = vec![];
+        let mut acc = gate_chip.load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        for i in 0..NUM_CARDS {
+            let power =
+                gate_chip.load_constant(layouter.namespace(|| "load power"), F::from(1 << i))?;
+            let bit = gate_chip.load_private(
+                layouter.namespace(|| "load dealt bit"),
+                Value::known(F::from(self.dealt >> i & 1)),
+            )?;
+            gate_chip.assert_bool(layouter.namespace(|| "dealt bit"), bit.clone())?;
+            acc = gate_chip.addmul(
+                layouter.namespace(|| "bit * power + acc"),
+                bit.clone(),
+                power.clone(),
+                acc,
+            )?;
+            powers.push(power);
+            dealt_bits.push(bit);
+        }
+        gate_chip.assert_equal(layouter.namespace(|| "dealt"), acc, dealt.clone())?;
+
+        let hand = self.hand();
+        let mut cards = vec![];
+        for (position, card) in self.positions.iter().zip(hand) {
+            let position = gate_chip.load_private(
+                layouter.namespace(|| "load position"),
+                Value::known(F::from(*position as u64)),
+            )?;
+            let flags = gate_chip.one_hot(layouter.namespace(|| "one hot"), position, NUM_CARDS)?;
+
+            // the position must not have been dealt yet, including earlier cards of this hand
+            let already_dealt = deck_chip.select_one_hot(
+                layouter.namespace(|| "already dealt"),
+                &flags,
+                &dealt_bits,
+            )?;
+            gate_chip.assert_constant(
+                layouter.namespace(|| "not dealt"),
+                already_dealt,
+                F::ZERO,
+            )?;
+            for (bit, flag) in dealt_bits.iter_mut().zip(flags.iter()) {
+                *bit = gate_chip.add(
+                    layouter.namespace(|| "mark dealt"),
+                    bit.clone(),
+                    flag.clone(),
+                )?;
+            }
+
+            let encrypted = deck_chip.select_one_hot(
+                layouter.namespace(|| "encrypted card"),
+                &flags,
+                &encrypted_cards,
+            )?;
+            let card = gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(F::from(card)),
+            )?;
+            deck_chip.assert_encryption(
+                layouter.namespace(|| "decryption"),
+                card.clone(),
+                key.clone(),
+                encrypted,
+            )?;
+            cards.push(card);
+        }
+
+        let dealt_after =
+            gate_chip.inner_product(layouter.namespace(|| "dealt after"), &dealt_bits, &powers)?;
+
+        let hand_salt = gate_chip.load_private(
+            layouter.namespace(|| "load hand salt"),
+            Value::known(self.hand_salt),
+        )?;
+        let hand_commitment = hand_chip.commit(
+            layouter.namespace(|| "hand commitment"),
+            context.session_id.clone(),
+            cards.try_into().unwrap(),
+            hand_salt,
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        let mut instance_offset = 0;
+        for slot in slots.iter() {
+            layouter.constrain_instance(slot.cell(), config.instance, instance_offset)?;
+            instance_offset += 1;
+        }
+        layouter.constrain_instance(key_commitment.cell(), config.instance, instance_offset)?;
+        layouter.constrain_instance(dealt.cell(), config.instance, instance_offset + 1)?;
+        layouter.constrain_instance(dealt_after.cell(), config.instance, instance_offset + 2)?;
+        layouter.constrain_instance(
+            hand_commitment.cell(),
+            config.instance,
+            instance_offset + 3,
+        )?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, instance_offset + 4)?;
+        Ok(())
+    }
+}
+
+impl<
+        F: FieldExt,
+        const NUM_CARDS: usize,
+        const WORD_BYTES: usize,
+        const FIELD_BYTES: usize,
+        const HAND_SIZE: usize,
+    > CircuitExt<F> for DealCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES, HAND_SIZE>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        let mut values = self.slots.clone();
+        values.push(key_commitment(
+            self.context.session_id,
+            self.key,
+            self.key_salt,
+        ));
+        values.push(F::from(self.dealt));
+        values.push(F::from(self.dealt_after()));
+        values.push(hand_commitment(
+            self.context.session_id,
+            self.hand(),
+            self.hand_salt,
+        ));
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    addmod_chip::AddModChip, distinct_single_key::unpack_slot, gate_chip::GateChip,
+    range_chip::RangeChip,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// Works on decks in the packed form produced by `DistinctSingleKeyCircuit`.
+pub struct DeckChip<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+> {
+    gate_chip: GateChip<F>,
+    range_chip: RangeChip<F, NUM_CARDS>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    DeckChip<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    pub fn from(gate_chip: GateChip<F>, range_chip: RangeChip<F, NUM_CARDS>) -> Self {
+        Self {
+            gate_chip,
+            range_chip,
+        }
+    }
+
+    /// Splits a packed slot into its `FIELD_BYTES` words, every word is range checked
+    /// to be a card index.
+    pub fn unpack_slot(
+        &self,
+        mut layouter: impl Layouter<F>,
+        slot: Cell<F>,
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let words = slot
+            .value()
+            .map(|slot| unpack_slot::<F, WORD_BYTES, FIELD_BYTES>(*slot));
+
+        let base = self.gate_chip.load_constant(
+            layouter.namespace(|| "load word base"),
+            F::from(1 << (WORD_BYTES * 8)),
+        )?;
+
+        let mut cells = vec![];
+        let mut acc: Option<Cell<F>> = None;
+        for i in 0..FIELD_BYTES {
+            let word = self.gate_chip.load_private(
+                layouter.namespace(|| "load word"),
+                words.as_ref().map(|words| F::from(words[i])),
+            )?;
+            self.range_chip
+                .range_constrain(layouter.namespace(|| "word range"), word.clone())?;
+
+            acc = Some(match acc {
+                None => word.clone(),
+                Some(acc) => self.gate_chip.addmul(
+                    layouter.namespace(|| "acc * base + word"),
+                    acc,
+                    base.clone(),
+                    word.clone(),
+                )?,
+            });
+            cells.push(word);
+        }
+
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "packed slot"), acc.unwrap(), slot)?;
+
+        Ok(cells)
+    }
+
+    /// Unpacks all slots and returns the first `NUM_CARDS` words.
+    pub fn unpack_deck(
+        &self,
+        mut layouter: impl Layouter<F>,
+        slots: &[Cell<F>],
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let mut cards = vec![];
+        for slot in slots {
+            cards.extend(self.unpack_slot(layouter.namespace(|| "unpack slot"), slot.clone())?);
+        }
+        cards.truncate(NUM_CARDS);
+        Ok(cards)
+    }
+
+    /// Packs cards into slots the way DSK exposes them, see
+    /// `distinct_single_key::pack_cards`. The last slot is padded with zero words, the
+    /// cards are expected to be range checked already.
+    pub fn pack_deck(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[Cell<F>],
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let base = self.gate_chip.load_constant(
+            layouter.namespace(|| "load word base"),
+            F::from(1 << (WORD_BYTES * 8)),
+        )?;
+        let zero = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+
+        let num_slots = NUM_CARDS * WORD_BYTES / FIELD_BYTES + 1;
+        let mut slots = vec![];
+        for i in 0..num_slots {
+            let mut acc = zero.clone();
+            for j in (FIELD_BYTES * i)..(FIELD_BYTES * (i + 1)) {
+                acc = self.gate_chip.addmul(
+                    layouter.namespace(|| "acc * base + word"),
+                    acc,
+                    base.clone(),
+                    cards.get(j).cloned().unwrap_or(zero.clone()),
+                )?;
+            }
+            slots.push(acc);
+        }
+        Ok(slots)
+    }
+
+    /// Returns `words[index]`.
+    pub fn select(
+        &self,
+        mut layouter: impl Layouter<F>,
+        words: &[Cell<F>],
+        index: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let flags = self
+            .gate_chip
+            .one_hot(layouter.namespace(|| "one hot"), index, words.len())?;
+        self.select_one_hot(layouter.namespace(|| "select"), &flags, words)
+    }
+
+    /// Returns the word whose flag is set, `flags` must come from [`GateChip::one_hot`].
+    pub fn select_one_hot(
+        &self,
+        mut layouter: impl Layouter<F>,
+        flags: &[Cell<F>],
+        words: &[Cell<F>],
+    ) -> Result<Cell<F>, Error> {
+        self.gate_chip
+            .inner_product(layouter.namespace(|| "flags . words"), flags, words)
+    }
+
+    /// Constrains `encrypted == (raw + key) % NUM_CARDS`, the DSK encryption relation.
+    pub fn assert_encryption(
+        &self,
+        mut layouter: impl Layouter<F>,
+        raw: Cell<F>,
+        key: Cell<F>,
+        encrypted: Cell<F>,
+    ) -> Result<(), Error> {
+        let addmod_chip = AddModChip::from(self.gate_chip.clone(), self.range_chip.clone());
+        let result = addmod_chip.addmod(layouter.namespace(|| "raw + key"), raw, key)?;
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "encryption"), result, encrypted)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    merkle::{MerkleProof, MerkleTree},
+    poseidon_chip::{poseidon_tagged, CardLeaf, DrawNullifier},
+};
+use halo2_utils::FieldExt;
+
+/// Leaf of an undealt card, drawn positions hold zero instead.
+pub fn card_leaf<F: FieldExt>(session_id: F, card: u64, deck_salt: F) -> F {
+    poseidon_tagged(CardLeaf, [session_id, F::from(card), deck_salt])
+}
+
+/// Public tag of a drawn position, the same position of a deck always gives the same
+/// nullifier so it can only be drawn once.
+pub fn draw_nullifier<F: FieldExt>(session_id: F, position: usize, deck_salt: F) -> F {
+    poseidon_tagged(
+        DrawNullifier,
+        [session_id, deck_salt, F::from(position as u64)],
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrawError {
+    /// The position is outside the deck or was drawn already.
+    InvalidPosition,
+    /// The draw does not start from the current root.
+    StaleRoot,
+    /// The nullifier was already spent.
+    AlreadyDrawn,
+}
+
+/// A draw as seen by the dealer, `old_root` and `proof` are the witness of `DrawCircuit`.
+#[derive(Debug, Clone)]
+pub struct Draw<F: FieldExt> {
+    pub card: u64,
+    pub proof: MerkleProof<F>,
+    pub old_root: F,
+    pub new_root: F,
+    pub nullifier: F,
+}
+
+/// Dealer side of a Merkle committed deck, the root only commits to the undealt cards of
+/// the session.
+#[derive(Debug, Clone)]
+pub struct DeckState<F: FieldExt> {
+    session_id: F,
+    cards: Vec<u64>,
+    deck_salt: F,
+    tree: MerkleTree<F>,
+    drawn: Vec<bool>,
+}
+
+impl<F: FieldExt> DeckState<F> {
+    pub fn new(session_id: F, cards: &[u64], deck_salt: F, depth: usize) -> Self {
+        let leaves = cards
+            .iter()
+            .map(|card| card_leaf(session_id, *card, deck_salt))
+            .collect::<Vec<_>>();
+        Self {
+            session_id,
+            cards: cards.to_vec(),
+            deck_salt,
+            tree: MerkleTree::new(&leaves, depth),
+            drawn: vec![false; cards.len()],
+        }
+    }
+
+    pub fn deck_salt(&self) -> F {
+        self.deck_salt
+    }
+
+    pub fn root(&self) -> F {
+        self.tree.root()
+    }
+
+    pub fn remaining(&self) -> Vec<usize> {
+        (0..self.cards.len()).filter(|i| !self.drawn[*i]).collect()
+    }
+
+    /// Draws the card at `position` and clears its leaf.
+    pub fn draw(&mut self, position: usize) -> Result<Draw<F>, DrawError> {
+        if position >= self.cards.len() || self.drawn[position] {
+            return Err(DrawError::InvalidPosition);
+        }
+
+        let old_root = self.tree.root();
+        let proof = self.tree.proof(position);
+        self.tree.set_leaf(position, F::ZERO);
+        self.drawn[position] = true;
+
+        Ok(Draw {
+            card: self.cards[position],
+            proof,
+            old_root,
+            new_root: self.tree.root(),
+            nullifier: draw_nullifier(self.session_id, position, self.deck_salt),
+        })
+    }
+}
+
+/// Client side of a deck, follows the roots and nullifiers published with each
+/// `DrawCircuit` proof. The caller verifies every proof against
+/// `[old_root, new_root, nullifier, card_commitment, context_digest]`.
+#[derive(Debug, Clone)]
+pub struct DrawAccumulator<F: FieldExt> {
+    root: F,
+    nullifiers: Vec<F>,
+}
+
+impl<F: FieldExt> DrawAccumulator<F> {
+    pub fn new(root: F) -> Self {
+        Self {
+            root,
+            nullifiers: vec![],
+        }
+    }
+
+    pub fn root(&self) -> F {
+        self.root
+    }
+
+    pub fn nullifiers(&self) -> &[F] {
+        &self.nullifiers
+    }
+
+    pub fn apply(&mut self, old_root: F, new_root: F, nullifier: F) -> Result<(), DrawError> {
+        if old_root != self.root {
+            return Err(DrawError::StaleRoot);
+        }
+        if self.nullifiers.contains(&nullifier) {
+            return Err(DrawError::AlreadyDrawn);
+        }
+        self.root = new_root;
+        self.nullifiers.push(nullifier);
+        Ok(())
+    }
+}
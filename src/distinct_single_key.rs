@@ -2,8 +2,8 @@
 #[allow(unused_variables)]
 use halo2_utils::{
     halo2_gadgets::poseidon::{
-        primitives::{self as poseidon, generate_constants, ConstantLength, Mds, Spec},
-        Hash, Pow5Chip, Pow5Config,
+        primitives::{generate_constants, Mds, Spec},
+        Pow5Chip, Pow5Config,
     },
     halo2_proofs::{
         circuit::{SimpleFloorPlanner, Value},
@@ -12,6 +12,11 @@ use halo2_utils::{
     },
     CircuitExt, Expr, FieldExt,
 };
+
+use crate::{
+    context::GameContext,
+    poseidon_chip::{poseidon_tagged, ContextDigest, KeyCommit, PoseidonChip},
+};
 #[allow(unused_imports)]
 #[allow(unused_variables)]
 use std::marker;
@@ -26,6 +31,7 @@ pub struct DistinctSingleKeyCircuit<
     pub raw_cards: [u64; NUM_CARDS],
     pub key: u64,
     pub key_salt: F,
+    pub context: GameContext<F>,
 }
 
 #[derive(Clone, Debug)]
@@ -174,7 +180,7 @@ impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_B
             },
         )?;
 
-        let (cells, key, key_salt) = layouter.assign_region(
+        let (cells, key, key_salt, session_id, move_nonce) = layouter.assign_region(
             || "witness",
             |mut region| {
                 let mut offset = 0;
@@ -229,10 +235,9 @@ impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_B
                 offset += NUM_CARDS;
 
                 // Assign addmod encryption
-                let mut encrypted_cards = [0; NUM_CARDS];
+                let encrypted_cards = encrypt_cards(self.raw_cards, self.key);
                 for i in 0..NUM_CARDS {
                     config.q_range_check.enable(&mut region, offset + i)?;
-                    encrypted_cards[i] = (self.raw_cards[i] + self.key) % NUM_CARDS as u64;
                     let encryption = F::from(encrypted_cards[i]);
                     region.assign_advice(
                         || "addmod cell",
@@ -258,19 +263,10 @@ impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_B
 
                 // final compressed value
                 let mut compressed_cells = vec![];
-                for i in 0..num_slots {
-                    let start = FIELD_BYTES * i;
-                    let end = FIELD_BYTES * (i + 1);
-                    let mut acc = F::ZERO;
-                    for j in start..end {
-                        if j < NUM_CARDS {
-                            acc =
-                                acc * F::from(1 << (WORD_BYTES * 8)) + F::from(encrypted_cards[j]);
-                        } else {
-                            acc *= F::from(1 << (WORD_BYTES * 8));
-                        }
-                    }
-
+                for (i, acc) in pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&encrypted_cards)
+                    .into_iter()
+                    .enumerate()
+                {
                     config
                         .q_compressor
                         .enable(&mut region, offset + i * FIELD_BYTES)?;
@@ -292,7 +288,26 @@ impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_B
                     || Value::known(self.key_salt),
                 )?;
 
-                Ok((compressed_cells, key.unwrap(), key_salt))
+                let session_id = region.assign_advice(
+                    || "session id",
+                    config.advice,
+                    offset + 1,
+                    || Value::known(self.context.session_id),
+                )?;
+                let move_nonce = region.assign_advice(
+                    || "move nonce",
+                    config.advice,
+                    offset + 2,
+                    || Value::known(F::from(self.context.move_nonce)),
+                )?;
+
+                Ok((
+                    compressed_cells,
+                    key.unwrap(),
+                    key_salt,
+                    session_id,
+                    move_nonce,
+                ))
             },
         )?;
 
@@ -304,15 +319,27 @@ impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_B
         }
         instance_offset += cells.len();
 
-        // Hash of key & key salt
-        let poseidon_chip = Pow5Chip::construct(config.poseidon.clone());
-        let hasher = Hash::<_, _, MySpec<F, 3, 2>, ConstantLength<2>, 3, 2>::init(
-            poseidon_chip,
-            layouter.namespace(|| "init"),
+        // the config was built with the same spec as `PoseidonChip`
+        let poseidon_chip = PoseidonChip::<F, 2> {
+            chip: Pow5Chip::construct(config.poseidon.clone()),
+        };
+
+        // Hash of session id, key & key salt
+        let output = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "hash"),
+            KeyCommit,
+            [session_id.clone(), key, key_salt],
         )?;
-        let output = hasher.hash(layouter.namespace(|| "hash"), [key, key_salt])?;
         layouter.constrain_instance(output.cell(), config.instance, instance_offset)?;
 
+        // Hash of the game context
+        let context_digest = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "hash context"),
+            ContextDigest,
+            [session_id, move_nonce],
+        )?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, instance_offset + 1)?;
+
         Ok(())
     }
 }
@@ -320,33 +347,15 @@ impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_B
 impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
     CircuitExt<F> for DistinctSingleKeyCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
 {
-    #[allow(clippy::needless_range_loop)]
     fn instances(&self) -> Vec<Vec<F>> {
-        // Assign addmod encryption
-        let mut encrypted_cards = [0; NUM_CARDS];
-        for i in 0..NUM_CARDS {
-            encrypted_cards[i] = (self.raw_cards[i] + self.key) % NUM_CARDS as u64;
-        }
-
-        let num_slots = NUM_CARDS * WORD_BYTES / FIELD_BYTES + 1;
-        let mut values = vec![];
-        for i in 0..num_slots {
-            let start = FIELD_BYTES * i;
-            let end = FIELD_BYTES * (i + 1);
-            let mut acc = F::ZERO;
-            for j in start..end {
-                if j < NUM_CARDS {
-                    acc = acc * F::from(1 << (WORD_BYTES * 8)) + F::from(encrypted_cards[j]);
-                } else {
-                    acc *= F::from(1 << (WORD_BYTES * 8));
-                }
-            }
-            values.push(acc);
-        }
-
-        let output = poseidon::Hash::<F, MySpec<F, 3, 2>, ConstantLength<2>, 3, 2>::init()
-            .hash([F::from(self.key), self.key_salt]);
-        values.push(output);
+        let encrypted_cards = encrypt_cards(self.raw_cards, self.key);
+        let mut values = pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&encrypted_cards);
+        values.push(key_commitment(
+            self.context.session_id,
+            self.key,
+            self.key_salt,
+        ));
+        values.push(self.context.digest());
 
         vec![values]
     }
@@ -361,6 +370,100 @@ impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_B
     // }
 }
 
+/// Shift cipher used by DSK, each card is encrypted as `(card + key) % NUM_CARDS`.
+pub fn encrypt_cards<const NUM_CARDS: usize>(
+    raw_cards: [u64; NUM_CARDS],
+    key: u64,
+) -> [u64; NUM_CARDS] {
+    raw_cards.map(|card| (card + key) % NUM_CARDS as u64)
+}
+
+/// Inverse of [`encrypt_cards`] for a single card.
+pub fn decrypt_card<const NUM_CARDS: usize>(encrypted_card: u64, key: u64) -> u64 {
+    let n = NUM_CARDS as u64;
+    (encrypted_card + n - key % n) % n
+}
+
+/// Inverse of [`encrypt_cards`].
+pub fn decrypt_cards<const NUM_CARDS: usize>(encrypted_cards: &[u64], key: u64) -> Vec<u64> {
+    encrypted_cards
+        .iter()
+        .map(|card| decrypt_card::<NUM_CARDS>(*card, key))
+        .collect()
+}
+
+/// Packs cards into field elements the way DSK exposes them as instances. Every slot
+/// holds `FIELD_BYTES` words of `WORD_BYTES` bytes, the first card being the most
+/// significant word. The last slot is padded with zero words.
+pub fn pack_cards<F: FieldExt, const WORD_BYTES: usize, const FIELD_BYTES: usize>(
+    cards: &[u64],
+) -> Vec<F> {
+    let num_slots = cards.len() * WORD_BYTES / FIELD_BYTES + 1;
+    let mut values = vec![];
+    for i in 0..num_slots {
+        let start = FIELD_BYTES * i;
+        let end = FIELD_BYTES * (i + 1);
+        let mut acc = F::ZERO;
+        for j in start..end {
+            acc *= F::from(1 << (WORD_BYTES * 8));
+            if j < cards.len() {
+                acc += F::from(cards[j]);
+            }
+        }
+        values.push(acc);
+    }
+    values
+}
+
+/// Splits a packed slot back into its `FIELD_BYTES` words, most significant first.
+pub fn unpack_slot<F: FieldExt, const WORD_BYTES: usize, const FIELD_BYTES: usize>(
+    slot: F,
+) -> Vec<u64> {
+    let repr = slot.to_repr();
+    let bytes = repr.as_ref();
+    (0..FIELD_BYTES)
+        .map(|j| {
+            let start = (FIELD_BYTES - 1 - j) * WORD_BYTES;
+            bytes[start..start + WORD_BYTES]
+                .iter()
+                .rev()
+                .fold(0, |acc, byte| acc << 8 | *byte as u64)
+        })
+        .collect()
+}
+
+/// Inverse of [`pack_cards`], returns the first `num_cards` words of the slots.
+pub fn unpack_cards<F: FieldExt, const WORD_BYTES: usize, const FIELD_BYTES: usize>(
+    slots: &[F],
+    num_cards: usize,
+) -> Vec<u64> {
+    slots
+        .iter()
+        .flat_map(|slot| unpack_slot::<F, WORD_BYTES, FIELD_BYTES>(*slot))
+        .take(num_cards)
+        .collect()
+}
+
+/// Decrypts a whole deck from the packed slots of the DSK instances, e.g. to audit
+/// it once the key has been opened with `KeyOpeningCircuit`.
+pub fn decrypt_deck<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+>(
+    slots: &[F],
+    key: u64,
+) -> Vec<u64> {
+    let encrypted_cards = unpack_cards::<F, WORD_BYTES, FIELD_BYTES>(slots, NUM_CARDS);
+    decrypt_cards::<NUM_CARDS>(&encrypted_cards, key)
+}
+
+/// Commitment to the DSK key of a session, exposed right before the context digest.
+pub fn key_commitment<F: FieldExt>(session_id: F, key: u64, key_salt: F) -> F {
+    poseidon_tagged(KeyCommit, [session_id, F::from(key), key_salt])
+}
+
 #[derive(Debug, Clone, Copy)]
 struct MySpec<F: FieldExt, const WIDTH: usize, const RATE: usize>(marker::PhantomData<F>);
 
@@ -0,0 +1,184 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    deck_state::{card_leaf, draw_nullifier, Draw},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    merkle::MerkleProof,
+    merkle_chip::MerkleChip,
+    poseidon_chip::{CardLeaf, DrawNullifier, PoseidonChip},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Draws the card at a private position of a `DeckState` of depth `DEPTH`.
+///
+/// The leaf is proven to be `Poseidon(session_id, card, deck_salt)` under the old root and
+/// the new root replaces it with zero, so a drawn position cannot be opened again. The
+/// nullifier `Poseidon(session_id, deck_salt, position)` makes a replayed draw visible to
+/// `DrawAccumulator`.
+///
+/// Instances are `[old_root, new_root, nullifier, card_commitment, context_digest]` where
+/// the card commitment is `hand_commitment(session_id, [card], card_salt)`.
+#[derive(Clone)]
+pub struct DrawCircuit<F: FieldExt, const DEPTH: usize> {
+    pub card: u64,
+    pub position: usize,
+    pub siblings: [F; DEPTH],
+    pub deck_salt: F,
+    pub card_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const DEPTH: usize> DrawCircuit<F, DEPTH> {
+    pub fn new(draw: &Draw<F>, deck_salt: F, card_salt: F, context: GameContext<F>) -> Self {
+        Self {
+            card: draw.card,
+            position: draw.proof.index,
+            siblings: draw.proof.siblings.clone().try_into().unwrap(),
+            deck_salt,
+            card_salt,
+            context,
+        }
+    }
+
+    fn proof(&self) -> MerkleProof<F> {
+        MerkleProof {
+            index: self.position,
+            siblings: self.siblings.to_vec(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DrawCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const DEPTH: usize> Circuit<F> for DrawCircuit<F, DEPTH> {
+    type Config = DrawCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        DrawCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let poseidon_chip = config.poseidon_chip;
+        let merkle_chip = MerkleChip::from(gate_chip.clone(), poseidon_chip.clone());
+        let hand_chip = HandChip::from(poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let card = gate_chip.load_private(
+            layouter.namespace(|| "load card"),
+            Value::known(F::from(self.card)),
+        )?;
+        let position = gate_chip.load_private(
+            layouter.namespace(|| "load position"),
+            Value::known(F::from(self.position as u64)),
+        )?;
+        let deck_salt = gate_chip.load_private(
+            layouter.namespace(|| "load deck salt"),
+            Value::known(self.deck_salt),
+        )?;
+        let mut siblings = vec![];
+        for sibling in self.siblings {
+            siblings.push(
+                gate_chip
+                    .load_private(layouter.namespace(|| "load sibling"), Value::known(sibling))?,
+            );
+        }
+
+        let leaf = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "card leaf"),
+            CardLeaf,
+            [context.session_id.clone(), card.clone(), deck_salt.clone()],
+        )?;
+        let old_root = merkle_chip.compute_root(
+            layouter.namespace(|| "old root"),
+            leaf,
+            position.clone(),
+            &siblings,
+        )?;
+
+        // the same path with the leaf cleared
+        let zero = gate_chip.load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        let new_root = merkle_chip.compute_root(
+            layouter.namespace(|| "new root"),
+            zero,
+            position.clone(),
+            &siblings,
+        )?;
+
+        let nullifier = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "nullifier"),
+            DrawNullifier,
+            [context.session_id.clone(), deck_salt, position],
+        )?;
+
+        let card_salt = gate_chip.load_private(
+            layouter.namespace(|| "load card salt"),
+            Value::known(self.card_salt),
+        )?;
+        let card_commitment = hand_chip.commit(
+            layouter.namespace(|| "card commitment"),
+            context.session_id.clone(),
+            [card],
+            card_salt,
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(old_root.cell(), config.instance, 0)?;
+        layouter.constrain_instance(new_root.cell(), config.instance, 1)?;
+        layouter.constrain_instance(nullifier.cell(), config.instance, 2)?;
+        layouter.constrain_instance(card_commitment.cell(), config.instance, 3)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 4)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const DEPTH: usize> CircuitExt<F> for DrawCircuit<F, DEPTH> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        let proof = self.proof();
+        vec![vec![
+            proof.root(card_leaf(
+                self.context.session_id,
+                self.card,
+                self.deck_salt,
+            )),
+            proof.root(F::ZERO),
+            draw_nullifier(self.context.session_id, self.position, self.deck_salt),
+            hand_commitment(self.context.session_id, [self.card], self.card_salt),
+            self.context.digest(),
+        ]]
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{
+    babyjubjub::{Point, A, D},
+    gate_chip::GateChip,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter, Value},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// A BabyJubJub point, see [`crate::babyjubjub`].
+#[derive(Clone, Debug)]
+pub struct AssignedPoint<F: FieldExt> {
+    pub x: Cell<F>,
+    pub y: Cell<F>,
+}
+
+impl<F: FieldExt> AssignedPoint<F> {
+    pub fn value(&self) -> Value<Point<F>> {
+        self.x
+            .value()
+            .zip(self.y.value())
+            .map(|(x, y)| Point { x: *x, y: *y })
+    }
+}
+
+/// BabyJubJub arithmetic built from `GateChip` operations.
+#[derive(Clone)]
+pub struct EccChip<F: FieldExt> {
+    gate_chip: GateChip<F>,
+}
+
+impl<F: FieldExt> EccChip<F> {
+    pub fn from(gate_chip: GateChip<F>) -> Self {
+        Self { gate_chip }
+    }
+
+    /// Loads a point and constrains it to be on the curve.
+    pub fn load_private(
+        &self,
+        mut layouter: impl Layouter<F>,
+        point: Value<Point<F>>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        let point = AssignedPoint {
+            x: self
+                .gate_chip
+                .load_private(layouter.namespace(|| "load x"), point.map(|p| p.x))?,
+            y: self
+                .gate_chip
+                .load_private(layouter.namespace(|| "load y"), point.map(|p| p.y))?,
+        };
+        self.assert_on_curve(layouter.namespace(|| "on curve"), point.clone())?;
+        Ok(point)
+    }
+
+    pub fn load_constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        point: Point<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        Ok(AssignedPoint {
+            x: self
+                .gate_chip
+                .load_constant(layouter.namespace(|| "load x"), point.x)?,
+            y: self
+                .gate_chip
+                .load_constant(layouter.namespace(|| "load y"), point.y)?,
+        })
+    }
+
+    /// Constrains `a x^2 + y^2 == 1 + d x^2 y^2`.
+    pub fn assert_on_curve(
+        &self,
+        mut layouter: impl Layouter<F>,
+        point: AssignedPoint<F>,
+    ) -> Result<(), Error> {
+        let a = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load a"), F::from(A))?;
+        let d = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load d"), F::from(D))?;
+        let one = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+
+        let x2 = self
+            .gate_chip
+            .mul(layouter.namespace(|| "x^2"), point.x.clone(), point.x)?;
+        let y2 = self
+            .gate_chip
+            .mul(layouter.namespace(|| "y^2"), point.y.clone(), point.y)?;
+        let lhs = self.gate_chip.addmul(
+            layouter.namespace(|| "a x^2 + y^2"),
+            a,
+            x2.clone(),
+            y2.clone(),
+        )?;
+        let x2y2 = self
+            .gate_chip
+            .mul(layouter.namespace(|| "x^2 y^2"), x2, y2)?;
+        let rhs = self
+            .gate_chip
+            .addmul(layouter.namespace(|| "1 + d x^2 y^2"), d, x2y2, one)?;
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "on curve"), lhs, rhs)
+    }
+
+    /// Constrains `point` to the prime subgroup by witnessing `Q` with `8 Q == point`, the
+    /// cofactor is 8 so the multiples of 8 are exactly the subgroup.
+    pub fn assert_in_subgroup(
+        &self,
+        mut layouter: impl Layouter<F>,
+        point: AssignedPoint<F>,
+    ) -> Result<(), Error> {
+        let mut multiple = self.load_private(
+            layouter.namespace(|| "load eighth"),
+            point.value().map(|point| point.eighth()),
+        )?;
+        for _ in 0..3 {
+            multiple = self.add(layouter.namespace(|| "double"), multiple.clone(), multiple)?;
+        }
+        self.assert_equal(layouter.namespace(|| "in subgroup"), multiple, point)
+    }
+
+    pub fn assert_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: AssignedPoint<F>,
+        b: AssignedPoint<F>,
+    ) -> Result<(), Error> {
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "x"), a.x, b.x)?;
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "y"), a.y, b.y)
+    }
+
+    /// Witnesses `numerator / denominator` and constrains it.
+    fn div(
+        &self,
+        mut layouter: impl Layouter<F>,
+        numerator: Cell<F>,
+        denominator: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let quotient = self.gate_chip.load_private(
+            layouter.namespace(|| "load quotient"),
+            numerator
+                .value()
+                .zip(denominator.value())
+                .map(|(n, d)| *n * d.invert().unwrap_or(F::ZERO)),
+        )?;
+        let product = self.gate_chip.mul(
+            layouter.namespace(|| "quotient * denominator"),
+            quotient.clone(),
+            denominator,
+        )?;
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "division"), product, numerator)?;
+        Ok(quotient)
+    }
+
+    /// Complete twisted Edwards addition, the denominators never vanish on BabyJubJub.
+    pub fn add(
+        &self,
+        mut layouter: impl Layouter<F>,
+        p: AssignedPoint<F>,
+        q: AssignedPoint<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        let d = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load d"), F::from(D))?;
+        let minus_d = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load -d"), -F::from(D))?;
+        let minus_a = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load -a"), -F::from(A))?;
+        let one = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+
+        let x1y2 = self
+            .gate_chip
+            .mul(layouter.namespace(|| "x1 y2"), p.x.clone(), q.y.clone())?;
+        let x_numerator = self.gate_chip.addmul(
+            layouter.namespace(|| "x1 y2 + y1 x2"),
+            p.y.clone(),
+            q.x.clone(),
+            x1y2,
+        )?;
+        let x1x2 = self
+            .gate_chip
+            .mul(layouter.namespace(|| "x1 x2"), p.x, q.x)?;
+        let y1y2 = self
+            .gate_chip
+            .mul(layouter.namespace(|| "y1 y2"), p.y, q.y)?;
+        let y_numerator = self.gate_chip.addmul(
+            layouter.namespace(|| "y1 y2 - a x1 x2"),
+            minus_a,
+            x1x2.clone(),
+            y1y2.clone(),
+        )?;
+
+        let t = self
+            .gate_chip
+            .mul(layouter.namespace(|| "x1 x2 y1 y2"), x1x2, y1y2)?;
+        let x_denominator =
+            self.gate_chip
+                .addmul(layouter.namespace(|| "1 + d t"), d, t.clone(), one.clone())?;
+        let y_denominator =
+            self.gate_chip
+                .addmul(layouter.namespace(|| "1 - d t"), minus_d, t, one)?;
+
+        Ok(AssignedPoint {
+            x: self.div(layouter.namespace(|| "x"), x_numerator, x_denominator)?,
+            y: self.div(layouter.namespace(|| "y"), y_numerator, y_denominator)?,
+        })
+    }
+
+    pub fn neg(
+        &self,
+        mut layouter: impl Layouter<F>,
+        point: AssignedPoint<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        let zero = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        Ok(AssignedPoint {
+            x: self
+                .gate_chip
+                .sub(layouter.namespace(|| "-x"), zero, point.x)?,
+            y: point.y,
+        })
+    }
+
+    pub fn sub(
+        &self,
+        mut layouter: impl Layouter<F>,
+        p: AssignedPoint<F>,
+        q: AssignedPoint<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        let q = self.neg(layouter.namespace(|| "-q"), q)?;
+        self.add(layouter.namespace(|| "p - q"), p, q)
+    }
+
+    /// Returns `p` if `condition` is 1 and `q` if it is 0.
+    pub fn select(
+        &self,
+        mut layouter: impl Layouter<F>,
+        condition: Cell<F>,
+        p: AssignedPoint<F>,
+        q: AssignedPoint<F>,
+    ) -> Result<AssignedPoint<F>, Error> {
+        Ok(AssignedPoint {
+            x: self.gate_chip.select(
+                layouter.namespace(|| "select x"),
+                condition.clone(),
+                p.x,
+                q.x,
+            )?,
+            y: self
+                .gate_chip
+                .select(layouter.namespace(|| "select y"), condition, p.y, q.y)?,
+        })
+    }
+
+    /// Double-and-add scalar multiplication over little-endian bits.
+    pub fn mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        point: AssignedPoint<F>,
+        bits: &[Cell<F>],
+    ) -> Result<AssignedPoint<F>, Error> {
+        let mut acc = self.load_constant(layouter.namespace(|| "identity"), Point::identity())?;
+        for bit in bits.iter().rev() {
+            acc = self.add(layouter.namespace(|| "double"), acc.clone(), acc)?;
+            let sum = self.add(layouter.namespace(|| "add"), acc.clone(), point.clone())?;
+            acc = self.select(layouter.namespace(|| "select"), bit.clone(), sum, acc)?;
+        }
+        Ok(acc)
+    }
+}
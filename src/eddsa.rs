@@ -0,0 +1,68 @@
+use crate::{
+    babyjubjub::{reduce_scalar, scalar_add, scalar_mul, Point},
+    poseidon_chip::{poseidon_circom, poseidon_tagged, EddsaNonce},
+};
+use halo2_utils::FieldExt;
+
+/// EdDSA signature over BabyJubJub, `r = k G` and `s = k + 8 h sk` modulo the subgroup
+/// order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature<F: FieldExt> {
+    pub r: Point<F>,
+    pub s: F,
+}
+
+/// Challenge `Poseidon(r.x, r.y, pk.x, pk.y, message)` of circomlib's EdDSA-Poseidon,
+/// hashed with [`poseidon_circom`] so circomlib signatures verify here and back.
+pub fn challenge<F: FieldExt>(r: Point<F>, public_key: Point<F>, message: F) -> F {
+    poseidon_circom::<F, 6, 5>([r.x, r.y, public_key.x, public_key.y, message])
+}
+
+/// Signing key, the secret is a scalar below the subgroup order and used as is.
+#[derive(Debug, Clone, Copy)]
+pub struct SigningKey<F: FieldExt> {
+    pub secret_key: F,
+    pub public_key: Point<F>,
+}
+
+impl<F: FieldExt> SigningKey<F> {
+    pub fn from(secret_key: F) -> Self {
+        let secret_key = reduce_scalar(secret_key);
+        Self {
+            secret_key,
+            public_key: Point::generator().mul(secret_key),
+        }
+    }
+
+    /// Deterministic signature, the nonce is derived from the key and the message. The
+    /// derivation differs from circomlib's, which hashes the private key with Blake-512,
+    /// so the same key and message give another valid signature.
+    pub fn sign(&self, message: F) -> Signature<F> {
+        let nonce = reduce_scalar(poseidon_tagged(EddsaNonce, [self.secret_key, message]));
+        let r = Point::generator().mul(nonce);
+        let h = challenge(r, self.public_key, message);
+        Signature {
+            r,
+            // circomlib's secret scalar is 8 sk, with pk = sk G
+            s: scalar_add(
+                nonce,
+                scalar_mul(scalar_mul(self.secret_key, F::from(8)), h),
+            ),
+        }
+    }
+}
+
+/// Checks `s G == r + 8 h pk`, circomlib's verification equation.
+pub fn verify<F: FieldExt>(public_key: Point<F>, message: F, signature: &Signature<F>) -> bool {
+    if !public_key.is_on_curve() || !signature.r.is_on_curve() {
+        return false;
+    }
+    let h = challenge(signature.r, public_key, message);
+    let left = Point::generator().mul(signature.s);
+    let right = signature.r.add(&times_eight(public_key).mul(h));
+    left == right
+}
+
+fn times_eight<F: FieldExt>(point: Point<F>) -> Point<F> {
+    (0..3).fold(point, |acc, _| acc.add(&acc))
+}
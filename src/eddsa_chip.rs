@@ -0,0 +1,98 @@
+use crate::{
+    babyjubjub::Point,
+    ecc_chip::{AssignedPoint, EccChip},
+    gate_chip::GateChip,
+    poseidon_chip::CircomPoseidonChip,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// Bits of the `s` half of a signature, the subgroup order has 251 bits.
+const SCALAR_BITS: usize = 251;
+
+/// In-circuit counterpart of [`crate::eddsa::Signature`].
+#[derive(Clone, Debug)]
+pub struct AssignedSignature<F: FieldExt> {
+    pub r: AssignedPoint<F>,
+    pub s: Cell<F>,
+}
+
+/// Verifies EdDSA-Poseidon signatures, see [`crate::eddsa::verify`].
+#[derive(Clone)]
+pub struct EddsaChip<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    ecc_chip: EccChip<F>,
+    poseidon_chip: CircomPoseidonChip<F, 6, 5>,
+}
+
+impl<F: FieldExt> EddsaChip<F> {
+    pub fn from(gate_chip: GateChip<F>, poseidon_chip: CircomPoseidonChip<F, 6, 5>) -> Self {
+        Self {
+            ecc_chip: EccChip::from(gate_chip.clone()),
+            gate_chip,
+            poseidon_chip,
+        }
+    }
+
+    /// Constrains `s G == r + 8 h pk` with `h = Poseidon(r.x, r.y, pk.x, pk.y, message)`.
+    /// The points are expected to be loaded with [`EccChip::load_private`] so they are on
+    /// the curve. `s` is only bounded by `2^251` and not by the subgroup order, so the
+    /// signature is malleable and must not be used as an identifier.
+    pub fn verify(
+        &self,
+        mut layouter: impl Layouter<F>,
+        public_key: AssignedPoint<F>,
+        message: Cell<F>,
+        signature: AssignedSignature<F>,
+    ) -> Result<(), Error> {
+        let h = self.poseidon_chip.hash(
+            layouter.namespace(|| "challenge"),
+            [
+                signature.r.x.clone(),
+                signature.r.y.clone(),
+                public_key.x.clone(),
+                public_key.y.clone(),
+                message,
+            ],
+        )?;
+
+        let generator = self
+            .ecc_chip
+            .load_constant(layouter.namespace(|| "generator"), Point::generator())?;
+        let s_bits =
+            self.gate_chip
+                .to_bits(layouter.namespace(|| "s bits"), signature.s, SCALAR_BITS)?;
+        let left = self
+            .ecc_chip
+            .mul(layouter.namespace(|| "s G"), generator, &s_bits)?;
+
+        let mut public_key = public_key;
+        for _ in 0..3 {
+            public_key = self.ecc_chip.add(
+                layouter.namespace(|| "double pk"),
+                public_key.clone(),
+                public_key,
+            )?;
+        }
+        // the canonical bits, h + p would be another multiple of the public key
+        let h_bits = self
+            .gate_chip
+            .to_bits_strict(layouter.namespace(|| "h bits"), h)?;
+        let hpk = self
+            .ecc_chip
+            .mul(layouter.namespace(|| "8 h pk"), public_key, &h_bits)?;
+        let right = self
+            .ecc_chip
+            .add(layouter.namespace(|| "r + 8 h pk"), signature.r, hpk)?;
+
+        self.ecc_chip
+            .assert_equal(layouter.namespace(|| "signature"), left, right)
+    }
+}
@@ -1,36 +1,84 @@
-use std::marker::PhantomData;
-
 use halo2_utils::{
     halo2_proofs::{
-        circuit::{Region, SimpleFloorPlanner, Value},
-        plonk::{Advice, Circuit, Column, Instance, Selector},
-        poly::Rotation,
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Error, Instance},
     },
     CircuitExt, FieldExt,
 };
 
 use crate::{
-    addmod_chip::AddModChip,
-    gate_chip::{self, GateChip},
-    poseidon_chip::{poseidon_sync, PoseidonChip},
-    range_chip::RangeConfig,
+    babyjubjub::Point,
+    barnett_smart::{encode_card, Ciphertext},
+    context::{ContextChip, GameContext},
+    ecc_chip::{AssignedPoint, EccChip},
+    gate_chip::GateChip,
+    poseidon_chip::PoseidonChip,
 };
 
-#[derive(Clone)]
-pub struct ElgamalCircuit<F: FieldExt, const N: usize> {
-    pub a: F,
-    pub b: F,
-    pub n: F,
-}
+/// Cards are encoded from their index plus one, which fits in this many bits.
+const CARD_BITS: usize = 8;
 
 #[derive(Clone)]
-pub struct ElgamalCircuitConfig<F: FieldExt, const N: usize> {
+pub struct ElgamalCircuitConfig<F: FieldExt> {
     gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
     instance: Column<Instance>,
 }
 
-impl<F: FieldExt, const N: usize> Circuit<F> for ElgamalCircuit<F, N> {
-    type Config = ElgamalCircuitConfig<F, N>;
+impl<F: FieldExt> ElgamalCircuitConfig<F> {
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        Self {
+            gate_chip,
+            poseidon_chip,
+            instance,
+        }
+    }
+}
+
+fn point_instances<F: FieldExt>(points: &[Point<F>]) -> Vec<F> {
+    points.iter().flat_map(|point| [point.x, point.y]).collect()
+}
+
+fn constrain_points<F: FieldExt>(
+    layouter: &mut impl Layouter<F>,
+    instance: Column<Instance>,
+    points: &[AssignedPoint<F>],
+    offset: usize,
+) -> Result<(), Error> {
+    for (i, point) in points.iter().enumerate() {
+        layouter.constrain_instance(point.x.cell(), instance, offset + 2 * i)?;
+        layouter.constrain_instance(point.y.cell(), instance, offset + 2 * i + 1)?;
+    }
+    Ok(())
+}
+
+/// Masks a public card under the aggregate key, see `barnett_smart::Ciphertext::mask`.
+///
+/// Instances are `[public_key, card, c1, c2, context_digest]` with points as `x, y`.
+#[derive(Clone)]
+pub struct MaskCircuit<F: FieldExt> {
+    pub public_key: Point<F>,
+    pub card: u64,
+    pub randomness: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt> MaskCircuit<F> {
+    pub fn ciphertext(&self) -> Ciphertext<F> {
+        Ciphertext::mask(encode_card(self.card), self.public_key, self.randomness)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MaskCircuit<F> {
+    type Config = ElgamalCircuitConfig<F>;
 
     type FloorPlanner = SimpleFloorPlanner;
 
@@ -39,18 +87,106 @@ impl<F: FieldExt, const N: usize> Circuit<F> for ElgamalCircuit<F, N> {
     }
 
     fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
-        let advice = meta.advice_column();
+        ElgamalCircuitConfig::configure(meta)
+    }
 
-        let gate_chip = GateChip::configure(meta, Some(advice));
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let ecc_chip = EccChip::from(gate_chip.clone());
 
-        let instance = meta.instance_column();
-        meta.enable_equality(instance);
+        let public_key = ecc_chip.load_private(
+            layouter.namespace(|| "load public key"),
+            Value::known(self.public_key),
+        )?;
+        let card = gate_chip.load_private(
+            layouter.namespace(|| "load card"),
+            Value::known(F::from(self.card)),
+        )?;
+        let randomness = gate_chip.load_private(
+            layouter.namespace(|| "load randomness"),
+            Value::known(self.randomness),
+        )?;
 
-        ElgamalCircuitConfig {
-            gate_chip,
+        let generator =
+            ecc_chip.load_constant(layouter.namespace(|| "generator"), Point::generator())?;
+        let one = gate_chip.load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        let encoded = gate_chip.add(layouter.namespace(|| "card + 1"), card.clone(), one)?;
+        let encoded = gate_chip.to_bits(layouter.namespace(|| "card bits"), encoded, CARD_BITS)?;
+        let card_point = ecc_chip.mul(
+            layouter.namespace(|| "card point"),
+            generator.clone(),
+            &encoded,
+        )?;
 
-            instance,
-        }
+        let randomness = gate_chip.to_bits(
+            layouter.namespace(|| "randomness bits"),
+            randomness,
+            F::NUM_BITS as usize,
+        )?;
+        let c1 = ecc_chip.mul(layouter.namespace(|| "r G"), generator, &randomness)?;
+        let shared = ecc_chip.mul(
+            layouter.namespace(|| "r PK"),
+            public_key.clone(),
+            &randomness,
+        )?;
+        let c2 = ecc_chip.add(layouter.namespace(|| "M + r PK"), card_point, shared)?;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        constrain_points(&mut layouter, config.instance, &[public_key], 0)?;
+        layouter.constrain_instance(card.cell(), config.instance, 2)?;
+        constrain_points(&mut layouter, config.instance, &[c1, c2], 3)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 7)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> CircuitExt<F> for MaskCircuit<F> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        let ciphertext = self.ciphertext();
+        let mut values = point_instances(&[self.public_key]);
+        values.push(F::from(self.card));
+        values.extend(point_instances(&[ciphertext.c1, ciphertext.c2]));
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
+
+/// Remasks a ciphertext under the aggregate key, see
+/// `barnett_smart::Ciphertext::remask`.
+///
+/// Instances are `[public_key, c1, c2, remasked c1, remasked c2, context_digest]` with
+/// points as `x, y`.
+#[derive(Clone)]
+pub struct RemaskCircuit<F: FieldExt> {
+    pub public_key: Point<F>,
+    pub ciphertext: Ciphertext<F>,
+    pub randomness: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt> RemaskCircuit<F> {
+    pub fn remasked(&self) -> Ciphertext<F> {
+        self.ciphertext.remask(self.public_key, self.randomness)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for RemaskCircuit<F> {
+    type Config = ElgamalCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        ElgamalCircuitConfig::configure(meta)
     }
 
     fn synthesize(
@@ -58,37 +194,67 @@ impl<F: FieldExt, const N: usize> Circuit<F> for ElgamalCircuit<F, N> {
         config: Self::Config,
         mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
     ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
-        let utils = config.gate_chip;
+        let gate_chip = config.gate_chip;
+        let ecc_chip = EccChip::from(gate_chip.clone());
 
-        let a = utils.load_private(layouter.namespace(|| "load a"), Value::known(self.a))?;
-        let b = utils.load_private(layouter.namespace(|| "load b"), Value::known(self.b))?;
+        let public_key = ecc_chip.load_private(
+            layouter.namespace(|| "load public key"),
+            Value::known(self.public_key),
+        )?;
+        let c1 = ecc_chip.load_private(
+            layouter.namespace(|| "load c1"),
+            Value::known(self.ciphertext.c1),
+        )?;
+        let c2 = ecc_chip.load_private(
+            layouter.namespace(|| "load c2"),
+            Value::known(self.ciphertext.c2),
+        )?;
+        let randomness = gate_chip.load_private(
+            layouter.namespace(|| "load randomness"),
+            Value::known(self.randomness),
+        )?;
 
-        // layouter.constrain_instance(final_hash.cell(), config.instance, 0)?;
+        let generator =
+            ecc_chip.load_constant(layouter.namespace(|| "generator"), Point::generator())?;
+        let randomness = gate_chip.to_bits(
+            layouter.namespace(|| "randomness bits"),
+            randomness,
+            F::NUM_BITS as usize,
+        )?;
+        let mask = ecc_chip.mul(layouter.namespace(|| "r G"), generator, &randomness)?;
+        let remasked_c1 = ecc_chip.add(layouter.namespace(|| "c1 + r G"), c1.clone(), mask)?;
+        let shared = ecc_chip.mul(
+            layouter.namespace(|| "r PK"),
+            public_key.clone(),
+            &randomness,
+        )?;
+        let remasked_c2 = ecc_chip.add(layouter.namespace(|| "c2 + r PK"), c2.clone(), shared)?;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        constrain_points(
+            &mut layouter,
+            config.instance,
+            &[public_key, c1, c2, remasked_c1, remasked_c2],
+            0,
+        )?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 10)?;
         Ok(())
     }
 }
 
-impl<F: FieldExt, const N: usize> CircuitExt<F> for ElgamalCircuit<F, N> {
-    // fn annotations(&self) -> (Vec<&str>, Vec<&str>, Vec<&str>, Vec<&str>) {
-    //     let grand_chip = GateChip::<F>::annotations();
-    //     (
-    //         grand_chip.0.iter().chain([].iter()).copied().collect(),
-    //         grand_chip.1.iter().chain([].iter()).copied().collect(),
-    //         grand_chip
-    //             .2
-    //             .iter()
-    //             .chain(["instance"].iter())
-    //             .copied()
-    //             .collect(),
-    //         grand_chip.3.iter().chain([].iter()).copied().collect(),
-    //     )
-    // }
-
+impl<F: FieldExt> CircuitExt<F> for RemaskCircuit<F> {
     fn instances(&self) -> Vec<Vec<F>> {
-        let intermediate = (self.a * self.b).square();
-        vec![vec![poseidon_sync([
-            self.a + poseidon_sync([intermediate, intermediate]),
-            self.a,
-        ])]]
+        let remasked = self.remasked();
+        let mut values = point_instances(&[
+            self.public_key,
+            self.ciphertext.c1,
+            self.ciphertext.c2,
+            remasked.c1,
+            remasked.c2,
+        ]);
+        values.push(self.context.digest());
+        vec![values]
     }
 }
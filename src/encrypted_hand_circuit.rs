@@ -0,0 +1,200 @@
+use crate::{
+    babyjubjub::Point,
+    cipher_chip::CipherChip,
+    context::{ContextChip, GameContext},
+    ecc_chip::EccChip,
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    poseidon_chip::{CipherKey, PoseidonChip},
+    poseidon_cipher::{encrypt, shared_key},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Sends a committed hand to a player, proves that the ciphertext holds the cards and the
+/// salt of `hand_commitment` so the player can open the commitment later.
+///
+/// The key is shared with the player through an ephemeral BabyJubJub key, the player
+/// decrypts with `shared_key(secret_key, ephemeral_key)`. The public key is constrained to
+/// the prime subgroup. The message is the cards followed by the hand salt.
+///
+/// Instances are `[public_key.x, public_key.y, ephemeral_key.x, ephemeral_key.y, nonce,
+/// hand_commitment, ciphertext.., context_digest]` with `HAND_SIZE + 2` ciphertext
+/// elements.
+#[derive(Clone)]
+pub struct EncryptedHandCircuit<F: FieldExt, const HAND_SIZE: usize> {
+    pub hand: [u64; HAND_SIZE],
+    pub hand_salt: F,
+    pub public_key: Point<F>,
+    pub ephemeral_secret: F,
+    pub nonce: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> EncryptedHandCircuit<F, HAND_SIZE> {
+    pub fn ephemeral_key(&self) -> Point<F> {
+        Point::generator().mul(self.ephemeral_secret)
+    }
+
+    pub fn message(&self) -> Vec<F> {
+        let mut message = self.hand.map(F::from).to_vec();
+        message.push(self.hand_salt);
+        message
+    }
+
+    pub fn ciphertext(&self) -> Vec<F> {
+        let key = shared_key(self.ephemeral_secret, self.public_key);
+        encrypt(key, self.nonce, &self.message())
+    }
+}
+
+#[derive(Clone)]
+pub struct EncryptedHandCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> Circuit<F> for EncryptedHandCircuit<F, HAND_SIZE> {
+    type Config = EncryptedHandCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        EncryptedHandCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let poseidon_chip = config.poseidon_chip;
+        let ecc_chip = EccChip::from(gate_chip.clone());
+        let hand_chip = HandChip::from(poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let cipher_chip = CipherChip::from(gate_chip.clone(), poseidon_chip.clone());
+
+        let mut hand = vec![];
+        for card in self.hand {
+            hand.push(gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(F::from(card)),
+            )?);
+        }
+        let hand_salt = gate_chip.load_private(
+            layouter.namespace(|| "load hand salt"),
+            Value::known(self.hand_salt),
+        )?;
+        let commitment = hand_chip.commit(
+            layouter.namespace(|| "hand commitment"),
+            context.session_id.clone(),
+            hand.clone().try_into().unwrap(),
+            hand_salt.clone(),
+        )?;
+
+        let public_key = ecc_chip.load_private(
+            layouter.namespace(|| "load public key"),
+            Value::known(self.public_key),
+        )?;
+        // a key of small order would leave only a few possible shared keys
+        ecc_chip.assert_in_subgroup(
+            layouter.namespace(|| "public key subgroup"),
+            public_key.clone(),
+        )?;
+        let ephemeral_secret = gate_chip.load_private(
+            layouter.namespace(|| "load ephemeral secret"),
+            Value::known(self.ephemeral_secret),
+        )?;
+        let ephemeral_secret = gate_chip.to_bits(
+            layouter.namespace(|| "ephemeral secret bits"),
+            ephemeral_secret,
+            F::NUM_BITS as usize,
+        )?;
+        let generator =
+            ecc_chip.load_constant(layouter.namespace(|| "generator"), Point::generator())?;
+        let ephemeral_key =
+            ecc_chip.mul(layouter.namespace(|| "e G"), generator, &ephemeral_secret)?;
+        let shared = ecc_chip.mul(
+            layouter.namespace(|| "e PK"),
+            public_key.clone(),
+            &ephemeral_secret,
+        )?;
+        let key = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "shared key"),
+            CipherKey,
+            [shared.x, shared.y],
+        )?;
+
+        let nonce = gate_chip.load_private(
+            layouter.namespace(|| "load nonce"),
+            Value::known(self.nonce),
+        )?;
+        let mut message = hand;
+        message.push(hand_salt);
+        let ciphertext = cipher_chip.encrypt(
+            layouter.namespace(|| "encrypt hand"),
+            key,
+            nonce.clone(),
+            &message,
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(public_key.x.cell(), config.instance, 0)?;
+        layouter.constrain_instance(public_key.y.cell(), config.instance, 1)?;
+        layouter.constrain_instance(ephemeral_key.x.cell(), config.instance, 2)?;
+        layouter.constrain_instance(ephemeral_key.y.cell(), config.instance, 3)?;
+        layouter.constrain_instance(nonce.cell(), config.instance, 4)?;
+        layouter.constrain_instance(commitment.cell(), config.instance, 5)?;
+        for (i, cell) in ciphertext.iter().enumerate() {
+            layouter.constrain_instance(cell.cell(), config.instance, 6 + i)?;
+        }
+        layouter.constrain_instance(
+            context_digest.cell(),
+            config.instance,
+            6 + ciphertext.len(),
+        )?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> CircuitExt<F> for EncryptedHandCircuit<F, HAND_SIZE> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        let ephemeral_key = self.ephemeral_key();
+        let mut values = vec![
+            self.public_key.x,
+            self.public_key.y,
+            ephemeral_key.x,
+            ephemeral_key.y,
+            self.nonce,
+            hand_commitment(self.context.session_id, self.hand, self.hand_salt),
+        ];
+        values.extend(self.ciphertext());
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
@@ -168,16 +168,19 @@ impl Circuit<Fr> for StandardPlonk {
     }
 }
 
-fn gen_srs(k: u32) -> ParamsKZG<Bn256> {
+pub(crate) fn gen_srs(k: u32) -> ParamsKZG<Bn256> {
     ParamsKZG::<Bn256>::setup(k, ChaChaRng::seed_from_u64(2))
 }
 
-fn gen_pk<C: Circuit<Fr>>(params: &ParamsKZG<Bn256>, circuit: &C) -> ProvingKey<G1Affine> {
+pub(crate) fn gen_pk<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    circuit: &C,
+) -> ProvingKey<G1Affine> {
     let vk = keygen_vk(params, circuit).unwrap();
     keygen_pk(params, vk, circuit).unwrap()
 }
 
-fn gen_proof<C: Circuit<Fr>>(
+pub(crate) fn gen_proof<C: Circuit<Fr>>(
     params: &ParamsKZG<Bn256>,
     pk: &ProvingKey<G1Affine>,
     circuit: C,
@@ -205,24 +208,30 @@ fn gen_proof<C: Circuit<Fr>>(
         transcript.finalize()
     };
 
-    let accept = {
-        let mut transcript = TranscriptReadBuffer::<_, G1Affine, _>::init(proof.as_slice());
-        VerificationStrategy::<_, VerifierGWC<_>>::finalize(
-            verify_proof::<_, VerifierGWC<_>, _, EvmTranscript<_, _, _, _>, _>(
-                params.verifier_params(),
-                pk.get_vk(),
-                AccumulatorStrategy::new(params.verifier_params()),
-                &[instances.as_slice()],
-                &mut transcript,
-            )
-            .unwrap(),
-        )
-    };
+    let accept = verify(params, pk.get_vk(), &instances, &proof);
     assert!(accept);
 
     proof
 }
 
+pub(crate) fn verify(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    instances: &[&[Fr]],
+    proof: &[u8],
+) -> bool {
+    let mut transcript = TranscriptReadBuffer::<_, G1Affine, _>::init(proof);
+    verify_proof::<_, VerifierGWC<_>, _, EvmTranscript<_, _, _, _>, _>(
+        params.verifier_params(),
+        vk,
+        AccumulatorStrategy::new(params.verifier_params()),
+        &[instances],
+        &mut transcript,
+    )
+    .map(VerificationStrategy::<_, VerifierGWC<_>>::finalize)
+    .unwrap_or(false)
+}
+
 fn gen_evm_verifier(
     params: &ParamsKZG<Bn256>,
     vk: &VerifyingKey<G1Affine>,
@@ -275,7 +284,8 @@ pub fn run<C: Clone + Circuit<Fr> + CircuitExt<Fr>>(k: u32, circuit: &C) {
     let params = gen_srs(k);
 
     let pk = gen_pk(&params, circuit);
-    let deployment_code = gen_evm_verifier(&params, pk.get_vk(), StandardPlonk::num_instance());
+    let num_instance = circuit.instances().iter().map(Vec::len).collect();
+    let deployment_code = gen_evm_verifier(&params, pk.get_vk(), num_instance);
     let bytecode_len = deployment_code.len();
 
     let proof = gen_proof(&params, &pk, circuit.clone(), circuit.instances());
@@ -0,0 +1,72 @@
+use crate::{
+    context::GameContext,
+    gate_chip::GateChip,
+    poseidon_chip::{poseidon_tagged, StateHash, StateWords},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// Game specific transition of a state of `STATE_SIZE` field elements, proven by
+/// `StateTransitionCircuit`. Invalid actions must be rejected by constraints in
+/// [`GameStep::synthesize`], the native [`GameStep::step`] only mirrors it.
+pub trait GameStep<F: FieldExt, const STATE_SIZE: usize> {
+    fn step(&self, state: [F; STATE_SIZE], action: F) -> [F; STATE_SIZE];
+
+    fn synthesize(
+        &self,
+        gate_chip: &GateChip<F>,
+        layouter: impl Layouter<F>,
+        state: [Cell<F>; STATE_SIZE],
+        action: Cell<F>,
+    ) -> Result<[Cell<F>; STATE_SIZE], Error>;
+}
+
+/// Hiding commitment to a game state, `Poseidon(session_id, Poseidon(state), salt)` with
+/// the outer hash in the [`StateHash`] domain and the inner one in the [`StateWords`]
+/// domain.
+pub fn state_hash<F: FieldExt, const STATE_SIZE: usize>(
+    session_id: F,
+    state: [F; STATE_SIZE],
+    salt: F,
+) -> F {
+    poseidon_tagged(
+        StateHash,
+        [session_id, poseidon_tagged(StateWords, state), salt],
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// The old state hash of a transition is not the new state hash of the one before it.
+    BrokenChain { step: usize },
+    /// The transition was proven for another session or move.
+    WrongContext { step: usize },
+}
+
+/// Checks that transitions given by their `StateTransitionCircuit` instances chain from
+/// `initial_hash`, transition `i` being move `i` after `context`, and returns the final
+/// state hash. Each proof is verified by the caller.
+pub fn check_chain<F: FieldExt>(
+    initial_hash: F,
+    context: GameContext<F>,
+    transitions: &[Vec<F>],
+) -> Result<F, ChainError> {
+    let mut hash = initial_hash;
+    for (step, instances) in transitions.iter().enumerate() {
+        if instances.len() != 4 || instances[0] != hash {
+            return Err(ChainError::BrokenChain { step });
+        }
+        if !context.advance(step as u64).check(instances) {
+            return Err(ChainError::WrongContext { step });
+        }
+        hash = instances[2];
+    }
+    Ok(hash)
+}
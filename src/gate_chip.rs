@@ -1,3 +1,4 @@
+use crate::utils::fe_to_bits;
 use halo2_utils::{
     halo2_proofs::{
         circuit::{AssignedCell, Layouter, Region, Value},
@@ -87,7 +88,7 @@ impl<F: FieldExt> GateChip<F> {
                 self.q_gate.enable(&mut region, 0)?;
 
                 a.copy_advice(|| "a", &mut region, self.advice, 0)?;
-                region.assign_advice(|| "b", self.advice, 1, || Value::known(F::ONE))?;
+                region.assign_advice_from_constant(|| "b", self.advice, 1, F::ONE)?;
                 c.copy_advice(|| "c", &mut region, self.advice, 2)?;
 
                 let value = a.value().copied() + c.value();
@@ -109,7 +110,7 @@ impl<F: FieldExt> GateChip<F> {
 
                 a.copy_advice(|| "a", &mut region, self.advice, 0)?;
                 b.copy_advice(|| "b", &mut region, self.advice, 1)?;
-                region.assign_advice(|| "c", self.advice, 2, || Value::known(F::ZERO))?;
+                region.assign_advice_from_constant(|| "c", self.advice, 2, F::ZERO)?;
 
                 let value = a.value().copied() * b.value();
                 region.assign_advice(|| "d", self.advice, 3, || value)
@@ -138,4 +139,206 @@ impl<F: FieldExt> GateChip<F> {
             },
         )
     }
+
+    pub fn assert_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assert equal",
+            |mut region: Region<'_, F>| region.constrain_equal(a.cell(), b.cell()),
+        )
+    }
+
+    pub fn assert_constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        constant: F,
+    ) -> Result<(), Error> {
+        let constant = self.load_constant(layouter.namespace(|| "load constant"), constant)?;
+        self.assert_equal(layouter.namespace(|| "assert constant"), a, constant)
+    }
+
+    /// Constrains `a` to be 0 or 1.
+    pub fn assert_bool(&self, mut layouter: impl Layouter<F>, a: Cell<F>) -> Result<(), Error> {
+        let square = self.mul(layouter.namespace(|| "a * a"), a.clone(), a.clone())?;
+        self.assert_equal(layouter.namespace(|| "a * a == a"), square, a)
+    }
+
+    /// Computes `a - b`.
+    pub fn sub(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let minus_one = self.load_constant(layouter.namespace(|| "load -1"), F::ONE.neg())?;
+        self.addmul(layouter.namespace(|| "a - b"), b, minus_one, a)
+    }
+
+    /// Returns a cell that is 1 if `a` is zero and 0 otherwise.
+    pub fn is_zero(&self, mut layouter: impl Layouter<F>, a: Cell<F>) -> Result<Cell<F>, Error> {
+        let inverse = self.load_private(
+            layouter.namespace(|| "load inverse"),
+            a.value().map(|a| a.invert().unwrap_or(F::ZERO)),
+        )?;
+        let is_zero = self.load_private(
+            layouter.namespace(|| "load is_zero"),
+            a.value()
+                .map(|a| if *a == F::ZERO { F::ONE } else { F::ZERO }),
+        )?;
+
+        // a * inverse + is_zero == 1
+        let one = self.addmul(
+            layouter.namespace(|| "a * inverse + is_zero"),
+            a.clone(),
+            inverse,
+            is_zero.clone(),
+        )?;
+        self.assert_constant(layouter.namespace(|| "must be one"), one, F::ONE)?;
+
+        // a * is_zero == 0
+        let zero = self.mul(layouter.namespace(|| "a * is_zero"), a, is_zero.clone())?;
+        self.assert_constant(layouter.namespace(|| "must be zero"), zero, F::ZERO)?;
+
+        Ok(is_zero)
+    }
+
+    /// Returns a cell that is 1 if `a == b` and 0 otherwise.
+    pub fn is_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let diff = self.sub(layouter.namespace(|| "diff"), a, b)?;
+        self.is_zero(layouter.namespace(|| "is zero"), diff)
+    }
+
+    /// Returns `a` if `condition` is 1 and `b` if it is 0. The condition is assumed to
+    /// be boolean.
+    pub fn select(
+        &self,
+        mut layouter: impl Layouter<F>,
+        condition: Cell<F>,
+        a: Cell<F>,
+        b: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let diff = self.sub(layouter.namespace(|| "a - b"), a, b.clone())?;
+        self.addmul(
+            layouter.namespace(|| "condition * (a - b) + b"),
+            condition,
+            diff,
+            b,
+        )
+    }
+
+    /// Computes `sum(a[i] * b[i])`.
+    pub fn inner_product(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[Cell<F>],
+        b: &[Cell<F>],
+    ) -> Result<Cell<F>, Error> {
+        let mut acc = self.load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        for (a, b) in a.iter().zip(b.iter()) {
+            acc = self.addmul(
+                layouter.namespace(|| "a * b + acc"),
+                a.clone(),
+                b.clone(),
+                acc,
+            )?;
+        }
+        Ok(acc)
+    }
+
+    /// Returns one-hot flags for `index` over `0..len`. Exactly one flag is set, which
+    /// also constrains `index` to be in range.
+    pub fn one_hot(
+        &self,
+        mut layouter: impl Layouter<F>,
+        index: Cell<F>,
+        len: usize,
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let mut flags = vec![];
+        let mut sum = self.load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        for i in 0..len {
+            let i = self.load_constant(layouter.namespace(|| "load i"), F::from(i as u64))?;
+            let flag = self.is_equal(layouter.namespace(|| "index == i"), index.clone(), i)?;
+            sum = self.add(layouter.namespace(|| "sum"), sum, flag.clone())?;
+            flags.push(flag);
+        }
+        self.assert_constant(layouter.namespace(|| "one flag set"), sum, F::ONE)?;
+        Ok(flags)
+    }
+
+    /// Decomposes a value into `num_bits` little-endian boolean cells, at most
+    /// `F::NUM_BITS` of them. With no bits the value must be zero.
+    pub fn to_bits(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Cell<F>,
+        num_bits: usize,
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let two = self.load_constant(layouter.namespace(|| "load two"), F::from(2))?;
+
+        let bits = value.value().map(|value| fe_to_bits(*value));
+
+        let mut cells = vec![];
+        let mut acc: Option<Cell<F>> = None;
+        for i in (0..num_bits).rev() {
+            let bit = self.load_private(
+                layouter.namespace(|| "load bit"),
+                bits.as_ref().map(|bits| F::from(bits[i] as u64)),
+            )?;
+            self.assert_bool(layouter.namespace(|| "bit"), bit.clone())?;
+            acc = Some(match acc {
+                None => bit.clone(),
+                Some(acc) => self.addmul(
+                    layouter.namespace(|| "acc * 2 + bit"),
+                    acc,
+                    two.clone(),
+                    bit.clone(),
+                )?,
+            });
+            cells.push(bit);
+        }
+        match acc {
+            Some(acc) => self.assert_equal(layouter.namespace(|| "recomposed"), acc, value)?,
+            None => self.assert_constant(layouter.namespace(|| "no bits"), value, F::ZERO)?,
+        }
+
+        cells.reverse();
+        Ok(cells)
+    }
+
+    /// Decomposes a value into its `F::NUM_BITS` little-endian bits and rejects the
+    /// decomposition of `value + p`, as circomlib's `Num2Bits_strict`.
+    pub fn to_bits_strict(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Cell<F>,
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let bits = self.to_bits(layouter.namespace(|| "bits"), value, F::NUM_BITS as usize)?;
+
+        // from the top bit down, as long as the bits match those of p - 1 the value must
+        // not set a bit that p - 1 leaves clear
+        let mut equal = self.load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        for (bit, max_bit) in bits.iter().zip(fe_to_bits(-F::ONE)).rev() {
+            if max_bit {
+                equal = self.mul(layouter.namespace(|| "still equal"), equal, bit.clone())?;
+            } else {
+                let above = self.mul(
+                    layouter.namespace(|| "above p - 1"),
+                    equal.clone(),
+                    bit.clone(),
+                )?;
+                self.assert_constant(layouter.namespace(|| "canonical"), above, F::ZERO)?;
+            }
+        }
+        Ok(bits)
+    }
 }
@@ -0,0 +1,59 @@
+use crate::poseidon_chip::{poseidon_tagged, HandCards, HandCommit, PoseidonChip};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// Commitment to a private hand, `Poseidon(session_id, Poseidon(cards), salt)` with the
+/// outer hash in the [`HandCommit`] domain and the inner one in the [`HandCards`] domain.
+/// The order of the cards matters.
+pub fn hand_commitment<F: FieldExt, const HAND_SIZE: usize>(
+    session_id: F,
+    cards: [u64; HAND_SIZE],
+    salt: F,
+) -> F {
+    poseidon_tagged(
+        HandCommit,
+        [
+            session_id,
+            poseidon_tagged(HandCards, cards.map(F::from)),
+            salt,
+        ],
+    )
+}
+
+#[derive(Clone)]
+pub struct HandChip<F: FieldExt> {
+    poseidon_chip: PoseidonChip<F, 2>,
+}
+
+impl<F: FieldExt> HandChip<F> {
+    pub fn from(poseidon_chip: PoseidonChip<F, 2>) -> Self {
+        Self { poseidon_chip }
+    }
+
+    /// In-circuit counterpart of [`hand_commitment`].
+    pub fn commit<const HAND_SIZE: usize>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        session_id: Cell<F>,
+        cards: [Cell<F>; HAND_SIZE],
+        salt: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let cards_hash = self.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "hash cards"),
+            HandCards,
+            cards,
+        )?;
+        self.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "hash with salt"),
+            HandCommit,
+            [session_id, cards_hash, salt],
+        )
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{card::Card, card_chip::AssignedCard, gate_chip::GateChip};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// The part of a card a predicate looks at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    Suit,
+    Rank,
+    /// The card index itself.
+    Card,
+}
+
+impl Attribute {
+    pub fn of(&self, card: Card, index: u64) -> u64 {
+        match self {
+            Attribute::Suit => card.suit,
+            Attribute::Rank => card.rank,
+            Attribute::Card => index,
+        }
+    }
+
+    fn of_cell<F: FieldExt>(&self, card: &AssignedCard<F>) -> Cell<F> {
+        match self {
+            Attribute::Suit => card.suit.clone(),
+            Attribute::Rank => card.rank.clone(),
+            Attribute::Card => card.index.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Predicate {
+    /// At least one card has the attribute.
+    Has,
+    /// No card has the attribute.
+    HasNone,
+    /// Exactly `count` cards have the attribute.
+    Count(u64),
+}
+
+impl Predicate {
+    pub fn holds(&self, count: u64) -> bool {
+        match self {
+            Predicate::Has => count > 0,
+            Predicate::HasNone => count == 0,
+            Predicate::Count(expected) => count == *expected,
+        }
+    }
+}
+
+/// Native counterpart of [`HandPredicateChip::count`], `cards` are `(card, index)` pairs.
+pub fn count_attribute(cards: &[(Card, u64)], attribute: Attribute, value: u64) -> u64 {
+    cards
+        .iter()
+        .filter(|(card, index)| attribute.of(*card, *index) == value)
+        .count() as u64
+}
+
+/// Evaluates predicates over the cards of a hand against a public attribute value.
+#[derive(Clone)]
+pub struct HandPredicateChip<F: FieldExt> {
+    gate_chip: GateChip<F>,
+}
+
+impl<F: FieldExt> HandPredicateChip<F> {
+    pub fn from(gate_chip: GateChip<F>) -> Self {
+        Self { gate_chip }
+    }
+
+    /// Number of cards whose `attribute` equals `value`.
+    pub fn count(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[AssignedCard<F>],
+        attribute: Attribute,
+        value: Cell<F>,
+    ) -> Result<Cell<F>, Error> {
+        let mut count = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        for card in cards {
+            let matches = self.gate_chip.is_equal(
+                layouter.namespace(|| "attribute == value"),
+                attribute.of_cell(card),
+                value.clone(),
+            )?;
+            count = self
+                .gate_chip
+                .add(layouter.namespace(|| "count"), count, matches)?;
+        }
+        Ok(count)
+    }
+
+    /// Constrains `predicate` to hold for `count`. For [`Predicate::Count`] the expected
+    /// count is passed as a cell so it can be public.
+    pub fn assert_predicate(
+        &self,
+        mut layouter: impl Layouter<F>,
+        predicate: Predicate,
+        count: Cell<F>,
+        expected: Option<Cell<F>>,
+    ) -> Result<(), Error> {
+        match predicate {
+            Predicate::Has => {
+                let is_zero = self
+                    .gate_chip
+                    .is_zero(layouter.namespace(|| "count is zero"), count)?;
+                self.gate_chip
+                    .assert_constant(layouter.namespace(|| "has"), is_zero, F::ZERO)
+            }
+            Predicate::HasNone => {
+                self.gate_chip
+                    .assert_constant(layouter.namespace(|| "has none"), count, F::ZERO)
+            }
+            Predicate::Count(_) => self.gate_chip.assert_equal(
+                layouter.namespace(|| "count"),
+                count,
+                expected.expect("count predicate needs an expected count"),
+            ),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    card::{DeckSpec, NUM_RANKS, NUM_SUITS},
+    card_chip::CardConfig,
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    hand_predicate_chip::{count_attribute, Attribute, HandPredicateChip, Predicate},
+    poseidon_chip::PoseidonChip,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Proves a predicate over a committed hand of standard deck cards, e.g. "I hold no
+/// card of the led suit" is `Attribute::Suit` with `Predicate::HasNone`.
+///
+/// Instances are `[hand_commitment, value]`, followed by the expected count for
+/// `Predicate::Count`, and the context digest last. The attribute and predicate kind
+/// are part of the circuit.
+#[derive(Clone)]
+pub struct HandPredicateCircuit<F: FieldExt, const HAND_SIZE: usize> {
+    pub hand: [u64; HAND_SIZE],
+    pub hand_salt: F,
+    pub attribute: Attribute,
+    pub predicate: Predicate,
+    pub value: u64,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> HandPredicateCircuit<F, HAND_SIZE> {
+    pub fn count(&self) -> u64 {
+        let cards = self
+            .hand
+            .map(|index| (DeckSpec::STANDARD.card(index).unwrap(), index));
+        count_attribute(&cards, self.attribute, self.value)
+    }
+}
+
+#[derive(Clone)]
+pub struct HandPredicateCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    card_config: CardConfig<F, NUM_SUITS, NUM_RANKS>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> Circuit<F> for HandPredicateCircuit<F, HAND_SIZE> {
+    type Config = HandPredicateCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let card_config = CardConfig::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        HandPredicateCircuitConfig {
+            gate_chip,
+            card_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let card_chip = config
+            .card_config
+            .construct(layouter.namespace(|| "card chip"), gate_chip.clone())?;
+        let hand_chip = HandChip::from(config.poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let predicate_chip = HandPredicateChip::from(gate_chip.clone());
+
+        let mut hand = vec![];
+        let mut cards = vec![];
+        for index in self.hand {
+            let index = gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(F::from(index)),
+            )?;
+            cards.push(card_chip.decompose(layouter.namespace(|| "decompose"), index.clone())?);
+            hand.push(index);
+        }
+        let hand_salt = gate_chip.load_private(
+            layouter.namespace(|| "load hand salt"),
+            Value::known(self.hand_salt),
+        )?;
+        let hand_commitment = hand_chip.commit(
+            layouter.namespace(|| "hand commitment"),
+            context.session_id.clone(),
+            hand.try_into().unwrap(),
+            hand_salt,
+        )?;
+
+        let value = gate_chip.load_private(
+            layouter.namespace(|| "load value"),
+            Value::known(F::from(self.value)),
+        )?;
+        let count = predicate_chip.count(
+            layouter.namespace(|| "count"),
+            &cards,
+            self.attribute,
+            value.clone(),
+        )?;
+        let expected = match self.predicate {
+            Predicate::Count(expected) => Some(gate_chip.load_private(
+                layouter.namespace(|| "load expected count"),
+                Value::known(F::from(expected)),
+            )?),
+            _ => None,
+        };
+        predicate_chip.assert_predicate(
+            layouter.namespace(|| "predicate"),
+            self.predicate,
+            count,
+            expected.clone(),
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(hand_commitment.cell(), config.instance, 0)?;
+        layouter.constrain_instance(value.cell(), config.instance, 1)?;
+        let mut instance_offset = 2;
+        if let Some(expected) = expected {
+            layouter.constrain_instance(expected.cell(), config.instance, instance_offset)?;
+            instance_offset += 1;
+        }
+        layouter.constrain_instance(context_digest.cell(), config.instance, instance_offset)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize> CircuitExt<F> for HandPredicateCircuit<F, HAND_SIZE> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        assert!(
+            self.predicate.holds(self.count()),
+            "predicate does not hold for the hand"
+        );
+        let mut values = vec![
+            hand_commitment(self.context.session_id, self.hand, self.hand_salt),
+            F::from(self.value),
+        ];
+        if let Predicate::Count(expected) = self.predicate {
+            values.push(F::from(expected));
+        }
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
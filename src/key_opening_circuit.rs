@@ -0,0 +1,110 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    distinct_single_key::key_commitment,
+    gate_chip::GateChip,
+    poseidon_chip::{KeyCommit, PoseidonChip},
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Opens the key commitment published by `DistinctSingleKeyCircuit` in the same session,
+/// the key salt stays private.
+///
+/// Instances are `[key_commitment, key, context_digest]`. Once verified, the whole deck can be
+/// decrypted with `distinct_single_key::decrypt_deck`.
+#[derive(Clone)]
+pub struct KeyOpeningCircuit<F: FieldExt, const NUM_CARDS: usize> {
+    pub key: u64,
+    pub key_salt: F,
+    pub context: GameContext<F>,
+}
+
+#[derive(Clone)]
+pub struct KeyOpeningCircuitConfig<F: FieldExt, const NUM_CARDS: usize> {
+    gate_chip: GateChip<F>,
+    range_config: RangeConfig<F, NUM_CARDS>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize> Circuit<F> for KeyOpeningCircuit<F, NUM_CARDS> {
+    type Config = KeyOpeningCircuitConfig<F, NUM_CARDS>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let range_config = RangeConfig::<F, NUM_CARDS>::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        KeyOpeningCircuitConfig {
+            gate_chip,
+            range_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+
+        let key = gate_chip.load_private(
+            layouter.namespace(|| "load key"),
+            Value::known(F::from(self.key)),
+        )?;
+        let key_salt = gate_chip.load_private(
+            layouter.namespace(|| "load key salt"),
+            Value::known(self.key_salt),
+        )?;
+
+        // same range DSK constrains the key to
+        range_chip.range_constrain(layouter.namespace(|| "key range"), key.clone())?;
+
+        let commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "key commitment"),
+            KeyCommit,
+            [context.session_id.clone(), key.clone(), key_salt],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(commitment.cell(), config.instance, 0)?;
+        layouter.constrain_instance(key.cell(), config.instance, 1)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 2)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize> CircuitExt<F> for KeyOpeningCircuit<F, NUM_CARDS> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            key_commitment(self.context.session_id, self.key, self.key_salt),
+            F::from(self.key),
+            self.context.digest(),
+        ]]
+    }
+}
@@ -0,0 +1,133 @@
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+use crate::{
+    babyjubjub::Point,
+    context::{ContextChip, GameContext},
+    ecc_chip::EccChip,
+    gate_chip::GateChip,
+    poseidon_chip::{poseidon_tagged, KeyRegistration, PoseidonChip},
+};
+
+/// Binds a public key to the table and seat it is registered for.
+pub fn registration_tag<F: FieldExt>(table_id: F, seat: u64, public_key: Point<F>) -> F {
+    poseidon_tagged(
+        KeyRegistration,
+        [table_id, F::from(seat), public_key.x, public_key.y],
+    )
+}
+
+/// Registers a player's key share at a table and proves knowledge of its secret key,
+/// so no player can pick a key that cancels the others out of the joint key.
+///
+/// Instances are `[public_key.x, public_key.y, registration_tag, context_digest]`, the
+/// tag keeps the proof from being replayed for another table or seat.
+#[derive(Clone)]
+pub struct KeyRegistrationCircuit<F: FieldExt> {
+    pub table_id: F,
+    pub seat: u64,
+    pub secret_key: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt> KeyRegistrationCircuit<F> {
+    pub fn public_key(&self) -> Point<F> {
+        Point::generator().mul(self.secret_key)
+    }
+}
+
+#[derive(Clone)]
+pub struct KeyRegistrationCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt> Circuit<F> for KeyRegistrationCircuit<F> {
+    type Config = KeyRegistrationCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        KeyRegistrationCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let ecc_chip = EccChip::from(gate_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let table_id = gate_chip.load_private(
+            layouter.namespace(|| "load table id"),
+            Value::known(self.table_id),
+        )?;
+        let seat = gate_chip.load_private(
+            layouter.namespace(|| "load seat"),
+            Value::known(F::from(self.seat)),
+        )?;
+        let secret_key = gate_chip.load_private(
+            layouter.namespace(|| "load secret key"),
+            Value::known(self.secret_key),
+        )?;
+
+        let generator =
+            ecc_chip.load_constant(layouter.namespace(|| "generator"), Point::generator())?;
+        let bits = gate_chip.to_bits(
+            layouter.namespace(|| "secret key bits"),
+            secret_key,
+            F::NUM_BITS as usize,
+        )?;
+        let public_key = ecc_chip.mul(layouter.namespace(|| "sk G"), generator, &bits)?;
+
+        let tag = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "registration tag"),
+            KeyRegistration,
+            [table_id, seat, public_key.x.clone(), public_key.y.clone()],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(public_key.x.cell(), config.instance, 0)?;
+        layouter.constrain_instance(public_key.y.cell(), config.instance, 1)?;
+        layouter.constrain_instance(tag.cell(), config.instance, 2)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 3)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> CircuitExt<F> for KeyRegistrationCircuit<F> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        let public_key = self.public_key();
+        vec![vec![
+            public_key.x,
+            public_key.y,
+            registration_tag(self.table_id, self.seat, public_key),
+            self.context.digest(),
+        ]]
+    }
+}
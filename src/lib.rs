@@ -1,13 +1,59 @@
 pub mod addmod_chip;
+pub mod babyjubjub;
+pub mod barnett_smart;
+pub mod blackjack;
+pub mod blackjack_circuit;
+pub mod card;
+pub mod card_chip;
+pub mod cipher_chip;
+pub mod compare_chip;
+pub mod context;
+pub mod cut_circuit;
+pub mod deal_circuit;
+pub mod deck_chip;
+pub mod deck_state;
 pub mod distinct_single_key;
+pub mod draw_circuit;
+pub mod ecc_chip;
+pub mod eddsa;
+pub mod eddsa_chip;
 pub mod elgamal_circuit;
+pub mod encrypted_hand_circuit;
 pub mod evm;
 pub mod exp_chip;
 #[allow(unused_imports)]
 #[allow(unused_variables)]
 pub mod first_circuit;
+pub mod game_step;
 pub mod gate_chip;
+pub mod hand_chip;
+pub mod hand_predicate_chip;
+pub mod hand_predicate_circuit;
+pub mod key_opening_circuit;
+pub mod key_registration_circuit;
+pub mod merkle;
+pub mod merkle_chip;
+pub mod multiset_chip;
+pub mod multiset_dsk_circuit;
+pub mod permutation_chip;
+pub mod play_card_circuit;
+pub mod poker;
+pub mod poker_chip;
 pub mod poseidon_chip;
+pub mod poseidon_cipher;
 pub mod preimage_circuit;
 pub mod range_chip;
+pub mod rekey_circuit;
+pub mod reshuffle_circuit;
+pub mod reveal_circuit;
+pub mod rummy;
+pub mod rummy_circuit;
+pub mod seating;
+pub mod seating_circuit;
+pub mod showdown_circuit;
+pub mod shuffle_protocol;
+pub mod shuffle_step_circuit;
+pub mod signed_action_circuit;
+pub mod state_transition_circuit;
+pub mod unmask_share_circuit;
 pub mod utils;
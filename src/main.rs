@@ -1,4 +1,3 @@
-mod distinct_single_key;
 #[allow(unused_imports)]
 use std::{path::PathBuf, rc::Rc, str::FromStr};
 
@@ -26,7 +25,7 @@ use halo2_utils::{
     },
 };
 
-use crate::distinct_single_key::DistinctSingleKeyCircuit;
+use zk_card::{context::GameContext, distinct_single_key::DistinctSingleKeyCircuit};
 
 fn main() {
     let raw_cards = randomize(std::array::from_fn(|i| i as u64), 20);
@@ -35,6 +34,7 @@ fn main() {
         raw_cards,
         key: 3,
         key_salt: Fr::from(0x1234),
+        context: GameContext::new(Fr::from(0x5e55), 0),
     };
     // let circuit = MyCircuit {
     //     a: Fr::from(3),
@@ -42,7 +42,7 @@ fn main() {
     //     _marker: std::marker::PhantomData,
     // };
 
-    let k = 7;
+    let k = 9;
 
     let prover = MockProver::run(k, &circuit, circuit.instances()).unwrap();
 
@@ -0,0 +1,89 @@
+use crate::poseidon_chip::{poseidon_tagged, MerkleNode};
+use halo2_utils::FieldExt;
+
+pub fn hash_pair<F: FieldExt>(left: F, right: F) -> F {
+    poseidon_tagged(MerkleNode, [left, right])
+}
+
+/// Poseidon Merkle tree of `2^depth` leaves, missing leaves are zero.
+#[derive(Debug, Clone)]
+pub struct MerkleTree<F: FieldExt> {
+    /// `levels[0]` are the leaves and the last level is the root.
+    levels: Vec<Vec<F>>,
+}
+
+impl<F: FieldExt> MerkleTree<F> {
+    pub fn new(leaves: &[F], depth: usize) -> Self {
+        assert!(leaves.len() <= 1 << depth, "too many leaves for the depth");
+
+        let mut level = leaves.to_vec();
+        level.resize(1 << depth, F::ZERO);
+        let mut levels = vec![level];
+        for _ in 0..depth {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hash_pair(pair[0], pair[1]))
+                .collect();
+            levels.push(level);
+        }
+        Self { levels }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn root(&self) -> F {
+        self.levels[self.depth()][0]
+    }
+
+    pub fn leaf(&self, index: usize) -> F {
+        self.levels[0][index]
+    }
+
+    pub fn set_leaf(&mut self, index: usize, leaf: F) {
+        self.levels[0][index] = leaf;
+        let mut index = index;
+        for depth in 0..self.depth() {
+            index /= 2;
+            let level = &self.levels[depth];
+            let node = hash_pair(level[2 * index], level[2 * index + 1]);
+            self.levels[depth + 1][index] = node;
+        }
+    }
+
+    pub fn proof(&self, index: usize) -> MerkleProof<F> {
+        let siblings = (0..self.depth())
+            .map(|depth| self.levels[depth][(index >> depth) ^ 1])
+            .collect();
+        MerkleProof { index, siblings }
+    }
+}
+
+/// Inclusion proof of the leaf at `index`, siblings are listed from the leaves up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof<F: FieldExt> {
+    pub index: usize,
+    pub siblings: Vec<F>,
+}
+
+impl<F: FieldExt> MerkleProof<F> {
+    pub fn root(&self, leaf: F) -> F {
+        self.siblings
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (depth, sibling)| {
+                if self.index >> depth & 1 == 0 {
+                    hash_pair(node, *sibling)
+                } else {
+                    hash_pair(*sibling, node)
+                }
+            })
+    }
+
+    pub fn verify(&self, root: F, leaf: F) -> bool {
+        self.root(leaf) == root
+    }
+}
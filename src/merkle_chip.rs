@@ -0,0 +1,81 @@
+use crate::{
+    gate_chip::GateChip,
+    poseidon_chip::{MerkleNode, PoseidonChip},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::Error,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// Verifies Poseidon Merkle paths of [`crate::merkle::MerkleTree`].
+#[derive(Clone)]
+pub struct MerkleChip<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+}
+
+impl<F: FieldExt> MerkleChip<F> {
+    pub fn from(gate_chip: GateChip<F>, poseidon_chip: PoseidonChip<F, 2>) -> Self {
+        Self {
+            gate_chip,
+            poseidon_chip,
+        }
+    }
+
+    /// Root of the path from `leaf` at `index` with `siblings` listed from the leaves up.
+    /// The index is decomposed into one bit per level, so it must be below
+    /// `2^siblings.len()`.
+    pub fn compute_root(
+        &self,
+        mut layouter: impl Layouter<F>,
+        leaf: Cell<F>,
+        index: Cell<F>,
+        siblings: &[Cell<F>],
+    ) -> Result<Cell<F>, Error> {
+        let bits =
+            self.gate_chip
+                .to_bits(layouter.namespace(|| "index bits"), index, siblings.len())?;
+
+        let mut node = leaf;
+        for (bit, sibling) in bits.into_iter().zip(siblings.iter()) {
+            // the node is on the right when the bit is set
+            let left = self.gate_chip.select(
+                layouter.namespace(|| "left"),
+                bit.clone(),
+                sibling.clone(),
+                node.clone(),
+            )?;
+            let right = self.gate_chip.select(
+                layouter.namespace(|| "right"),
+                bit,
+                node,
+                sibling.clone(),
+            )?;
+            node = self.poseidon_chip.hash_tagged(
+                layouter.namespace(|| "hash pair"),
+                MerkleNode,
+                [left, right],
+            )?;
+        }
+        Ok(node)
+    }
+
+    /// Constrains `leaf` to be at `index` of the tree with `root`.
+    pub fn assert_inclusion(
+        &self,
+        mut layouter: impl Layouter<F>,
+        root: Cell<F>,
+        leaf: Cell<F>,
+        index: Cell<F>,
+        siblings: &[Cell<F>],
+    ) -> Result<(), Error> {
+        let computed = self.compute_root(layouter.namespace(|| "root"), leaf, index, siblings)?;
+        self.gate_chip
+            .assert_equal(layouter.namespace(|| "same root"), computed, root)
+    }
+}
@@ -0,0 +1,236 @@
+use crate::utils::fe_to_u64;
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter, Value},
+        plonk::{Advice, Column, Error, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// How many times every card value appears in a deck, e.g. two copies of 24 cards for
+/// Pinochle. Each copy of a card gets a distinct tag, tags list the cards in order so
+/// copies of the same card have consecutive tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multiplicities {
+    counts: Vec<u64>,
+}
+
+impl Multiplicities {
+    pub fn new(counts: Vec<u64>) -> Self {
+        assert!(
+            counts.iter().all(|count| *count > 0),
+            "every card must appear at least once"
+        );
+        Self { counts }
+    }
+
+    /// `copies` copies of `distinct` cards.
+    pub fn uniform(distinct: usize, copies: u64) -> Self {
+        Self::new(vec![copies; distinct])
+    }
+
+    pub fn size(&self) -> usize {
+        self.counts.iter().sum::<u64>() as usize
+    }
+
+    /// The card of every tag.
+    pub fn cards(&self) -> Vec<u64> {
+        self.counts
+            .iter()
+            .enumerate()
+            .flat_map(|(card, count)| (0..*count).map(move |_| card as u64))
+            .collect()
+    }
+
+    pub fn card(&self, tag: u64) -> Option<u64> {
+        self.cards().get(tag as usize).copied()
+    }
+
+    /// Tags the cards of a deck, the n-th copy of a card gets the n-th tag of that card.
+    /// `None` if a card appears more often than its multiplicity.
+    pub fn tags(&self, cards: &[u64]) -> Option<Vec<u64>> {
+        let firsts = self
+            .counts
+            .iter()
+            .scan(0, |first, count| {
+                let tag = *first;
+                *first += count;
+                Some(tag)
+            })
+            .collect::<Vec<_>>();
+
+        let mut used = vec![0; self.counts.len()];
+        cards
+            .iter()
+            .map(|card| {
+                let card = *card as usize;
+                let copy = used.get_mut(card)?;
+                (*copy < self.counts[card]).then(|| {
+                    *copy += 1;
+                    firsts[card] + *copy - 1
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MultisetConfig<F: FieldExt> {
+    q_tag: Selector,
+    tag_table: Column<Fixed>,
+    card_table: Column<Fixed>,
+    advice: Column<Advice>,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: FieldExt> MultisetConfig<F> {
+    pub fn configure(
+        meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>,
+        advice: Option<Column<Advice>>,
+    ) -> Self {
+        let q_tag = meta.complex_selector();
+        let tag_table = meta.fixed_column();
+        let card_table = meta.fixed_column();
+        let advice = advice.unwrap_or(meta.advice_column());
+
+        // The tables hold `tag + 1` so that disabled rows, which look up 0, cannot stand
+        // in for a tag. With as many tagged rows as tags, every tag is used exactly once.
+        meta.lookup_any("every tag must be used", |meta| {
+            // fixed table
+            let tag_table = meta.query_fixed(tag_table, Rotation::cur());
+
+            // witness
+            let q_tag = meta.query_selector(q_tag);
+            let tag = meta.query_advice(advice, Rotation::cur());
+
+            vec![(tag_table, q_tag * (tag + Expression::Constant(F::ONE)))]
+        });
+
+        meta.lookup_any("card must match its tag", |meta| {
+            // witness
+            let q_tag = meta.query_selector(q_tag);
+            let tag = meta.query_advice(advice, Rotation::cur());
+            let card = meta.query_advice(advice, Rotation::next());
+
+            // fixed table
+            let tag_table = meta.query_fixed(tag_table, Rotation::cur());
+            let card_table = meta.query_fixed(card_table, Rotation::cur());
+
+            vec![
+                (
+                    q_tag.clone() * (tag + Expression::Constant(F::ONE)),
+                    tag_table,
+                ),
+                (q_tag * card, card_table),
+            ]
+        });
+
+        Self {
+            q_tag,
+            tag_table,
+            card_table,
+            advice,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn construct(
+        &self,
+        mut layouter: impl Layouter<F>,
+        multiplicities: Multiplicities,
+    ) -> Result<MultisetChip<F>, Error> {
+        layouter.assign_region(
+            || "multiset table",
+            |mut region| {
+                // first row is what disabled rows look up
+                region.assign_fixed(|| "tag", self.tag_table, 0, || Value::known(F::ZERO))?;
+                region.assign_fixed(|| "card", self.card_table, 0, || Value::known(F::ZERO))?;
+                for (tag, card) in multiplicities.cards().into_iter().enumerate() {
+                    region.assign_fixed(
+                        || "tag",
+                        self.tag_table,
+                        tag + 1,
+                        || Value::known(F::from(tag as u64 + 1)),
+                    )?;
+                    region.assign_fixed(
+                        || "card",
+                        self.card_table,
+                        tag + 1,
+                        || Value::known(F::from(card)),
+                    )?;
+                }
+                Ok(())
+            },
+        )?;
+
+        Ok(MultisetChip {
+            config: self.clone(),
+            multiplicities,
+        })
+    }
+}
+
+/// Multiset counterpart of the DSK uniqueness lookup, proves that a deck holds every
+/// card exactly as many times as its [`Multiplicities`].
+#[derive(Clone, Debug)]
+pub struct MultisetChip<F: FieldExt> {
+    config: MultisetConfig<F>,
+    multiplicities: Multiplicities,
+}
+
+impl<F: FieldExt> MultisetChip<F> {
+    pub fn multiplicities(&self) -> &Multiplicities {
+        &self.multiplicities
+    }
+
+    /// Constrains `cards` to be a permutation of the multiset and returns the tag of
+    /// every card, which together form a permutation of `0..size`. The lookup spans
+    /// the whole circuit, so this must be called once per circuit.
+    pub fn assert_multiset(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[Cell<F>],
+    ) -> Result<Vec<Cell<F>>, Error> {
+        assert_eq!(
+            cards.len(),
+            self.multiplicities.size(),
+            "deck size must match the multiplicities"
+        );
+
+        let tags = cards
+            .iter()
+            .fold(Value::known(vec![]), |acc, card| {
+                acc.zip(card.value()).map(|(mut acc, card)| {
+                    acc.push(fe_to_u64(*card));
+                    acc
+                })
+            })
+            .map(|cards| self.multiplicities.tags(&cards));
+
+        layouter.assign_region(
+            || "multiset",
+            |mut region| {
+                let mut tag_cells = vec![];
+                for (i, card) in cards.iter().enumerate() {
+                    self.config.q_tag.enable(&mut region, 2 * i)?;
+                    tag_cells.push(region.assign_advice(
+                        || "tag",
+                        self.config.advice,
+                        2 * i,
+                        || {
+                            tags.as_ref().map(|tags| {
+                                // an invalid deck fails the lookups
+                                F::from(tags.as_ref().map_or(u64::MAX, |tags| tags[i]))
+                            })
+                        },
+                    )?);
+                    card.copy_advice(|| "card", &mut region, self.config.advice, 2 * i + 1)?;
+                }
+                Ok(tag_cells)
+            },
+        )
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    addmod_chip::AddModChip,
+    context::{ContextChip, GameContext},
+    deck_chip::DeckChip,
+    distinct_single_key::{encrypt_cards, key_commitment, pack_cards},
+    gate_chip::GateChip,
+    multiset_chip::{Multiplicities, MultisetConfig},
+    poseidon_chip::{KeyCommit, PoseidonChip},
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Multi-deck variant of `DistinctSingleKeyCircuit` for games such as Canasta or
+/// Pinochle where cards appear several times. Instead of the raw cards being distinct,
+/// they must contain every card as many times as given by `multiplicities`.
+///
+/// The DSK shift cipher is applied to the tags of the cards, which are a permutation
+/// of `0..NUM_CARDS`, and the instances have the same layout as DSK:
+/// `[slots.., key_commitment, context_digest]`. Decrypted tags map back to cards with
+/// [`Multiplicities::card`].
+#[derive(Debug, Clone)]
+pub struct MultisetDSKCircuit<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+> {
+    pub raw_cards: [u64; NUM_CARDS],
+    pub multiplicities: Multiplicities,
+    pub key: u64,
+    pub key_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    MultisetDSKCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    pub fn encrypted_tags(&self) -> [u64; NUM_CARDS] {
+        let tags = self
+            .multiplicities
+            .tags(&self.raw_cards)
+            .expect("raw cards must match the multiplicities");
+        encrypt_cards(tags.try_into().unwrap(), self.key)
+    }
+}
+
+#[derive(Clone)]
+pub struct MultisetDSKCircuitConfig<F: FieldExt, const NUM_CARDS: usize> {
+    gate_chip: GateChip<F>,
+    range_config: RangeConfig<F, NUM_CARDS>,
+    multiset_config: MultisetConfig<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    Circuit<F> for MultisetDSKCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    type Config = MultisetDSKCircuitConfig<F, NUM_CARDS>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let range_config = RangeConfig::configure(meta, Some(advice));
+        let multiset_config = MultisetConfig::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        MultisetDSKCircuitConfig {
+            gate_chip,
+            range_config,
+            multiset_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        assert_eq!(
+            self.multiplicities.size(),
+            NUM_CARDS,
+            "multiplicities must add up to NUM_CARDS"
+        );
+
+        let gate_chip = config.gate_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let multiset_chip = config.multiset_config.construct(
+            layouter.namespace(|| "multiset chip"),
+            self.multiplicities.clone(),
+        )?;
+        let addmod_chip = AddModChip::from(gate_chip.clone(), range_chip.clone());
+        let deck_chip =
+            DeckChip::<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>::from(gate_chip.clone(), range_chip);
+
+        let mut raw_cards = vec![];
+        for card in self.raw_cards {
+            raw_cards.push(gate_chip.load_private(
+                layouter.namespace(|| "load raw card"),
+                Value::known(F::from(card)),
+            )?);
+        }
+        let tags = multiset_chip.assert_multiset(layouter.namespace(|| "multiset"), &raw_cards)?;
+
+        let key = gate_chip.load_private(
+            layouter.namespace(|| "load key"),
+            Value::known(F::from(self.key)),
+        )?;
+        let key_salt = gate_chip.load_private(
+            layouter.namespace(|| "load key salt"),
+            Value::known(self.key_salt),
+        )?;
+
+        let mut encrypted = vec![];
+        for tag in tags {
+            encrypted.push(addmod_chip.addmod(
+                layouter.namespace(|| "tag + key"),
+                tag,
+                key.clone(),
+            )?);
+        }
+
+        let slots = deck_chip.pack_deck(layouter.namespace(|| "pack deck"), &encrypted)?;
+
+        let key_commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "key commitment"),
+            KeyCommit,
+            [context.session_id.clone(), key, key_salt],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        for (i, slot) in slots.iter().enumerate() {
+            layouter.constrain_instance(slot.cell(), config.instance, i)?;
+        }
+        layouter.constrain_instance(key_commitment.cell(), config.instance, slots.len())?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, slots.len() + 1)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    CircuitExt<F> for MultisetDSKCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        let mut values = pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.encrypted_tags());
+        values.push(key_commitment(
+            self.context.session_id,
+            self.key,
+            self.key_salt,
+        ));
+        values.push(self.context.digest());
+
+        vec![values]
+    }
+}
@@ -0,0 +1,126 @@
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter, Value},
+        plonk::{Advice, Challenge, Column, Error, Expression, FirstPhase, SecondPhase, Selector},
+        poly::Rotation,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+#[derive(Clone, Debug)]
+pub struct PermutationConfig<F: FieldExt> {
+    q_first: Selector,
+    q_step: Selector,
+    q_last: Selector,
+    a: Column<Advice>,
+    b: Column<Advice>,
+    z: Column<Advice>,
+    challenge: Challenge,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: FieldExt> PermutationConfig<F> {
+    pub fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self {
+        let q_first = meta.selector();
+        let q_step = meta.selector();
+        let q_last = meta.selector();
+        let a = meta.advice_column_in(FirstPhase);
+        let b = meta.advice_column_in(FirstPhase);
+        let z = meta.advice_column_in(SecondPhase);
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+
+        // drawn once both sides are committed to
+        let challenge = meta.challenge_usable_after(FirstPhase);
+
+        meta.create_gate("product starts at one", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let z = meta.query_advice(z, Rotation::cur());
+            vec![q_first * (z - Expression::Constant(F::ONE))]
+        });
+
+        meta.create_gate("product step", |meta| {
+            let q_step = meta.query_selector(q_step);
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            let z_cur = meta.query_advice(z, Rotation::cur());
+            let z_next = meta.query_advice(z, Rotation::next());
+            let x = meta.query_challenge(challenge);
+            vec![q_step * (z_next * (x.clone() - b) - z_cur * (x - a))]
+        });
+
+        meta.create_gate("product ends at one", |meta| {
+            let q_last = meta.query_selector(q_last);
+            let z = meta.query_advice(z, Rotation::cur());
+            vec![q_last * (z - Expression::Constant(F::ONE))]
+        });
+
+        Self {
+            q_first,
+            q_step,
+            q_last,
+            a,
+            b,
+            z,
+            challenge,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn construct(&self) -> PermutationChip<F> {
+        PermutationChip {
+            config: self.clone(),
+        }
+    }
+}
+
+/// Grand product permutation argument, unlike the fixed table lookup of DSK both sides
+/// can be witnesses. With `X` a challenge drawn after the values are committed, the
+/// running product `z` proves `∏(X - a_i) = ∏(X - b_i)`.
+#[derive(Clone, Debug)]
+pub struct PermutationChip<F: FieldExt> {
+    config: PermutationConfig<F>,
+}
+
+impl<F: FieldExt> PermutationChip<F> {
+    /// Constrains `b` to be a permutation of `a`.
+    pub fn assert_permutation(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &[Cell<F>],
+        b: &[Cell<F>],
+    ) -> Result<(), Error> {
+        assert_eq!(a.len(), b.len(), "both sides must have the same length");
+
+        let x = layouter.get_challenge(self.config.challenge);
+
+        layouter.assign_region(
+            || "grand product",
+            |mut region| {
+                self.config.q_first.enable(&mut region, 0)?;
+                let mut z = Value::known(F::ONE);
+                region.assign_advice(|| "z", self.config.z, 0, || z)?;
+
+                for (i, (a, b)) in a.iter().zip(b.iter()).enumerate() {
+                    self.config.q_step.enable(&mut region, i)?;
+                    a.copy_advice(|| "a", &mut region, self.config.a, i)?;
+                    b.copy_advice(|| "b", &mut region, self.config.b, i)?;
+
+                    z = z
+                        .zip(x)
+                        .zip(a.value().zip(b.value()))
+                        .map(|((z, x), (a, b))| {
+                            // a zero denominator only happens with negligible probability
+                            z * (x - a) * (x - b).invert().unwrap_or(F::ZERO)
+                        });
+                    region.assign_advice(|| "z", self.config.z, i + 1, || z)?;
+                }
+
+                self.config.q_last.enable(&mut region, a.len())?;
+                Ok(())
+            },
+        )
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    poseidon_chip::PoseidonChip,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Plays one card out of a committed hand and commits to the remaining cards, which
+/// keep their order. `REMAINING` must be `HAND_SIZE - 1`.
+///
+/// The played card is selected from the committed cards so it cannot be conjured. The
+/// cards of the hand are checked pairwise distinct, so a hand listing a card twice cannot
+/// play it and keep it in the remaining hand.
+///
+/// Instances are `[hand_commitment, played_card, remaining_commitment, context_digest]`.
+#[derive(Clone)]
+pub struct PlayCardCircuit<F: FieldExt, const HAND_SIZE: usize, const REMAINING: usize> {
+    pub hand: [u64; HAND_SIZE],
+    pub hand_salt: F,
+    pub played_index: usize,
+    pub remaining_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize, const REMAINING: usize>
+    PlayCardCircuit<F, HAND_SIZE, REMAINING>
+{
+    pub fn played_card(&self) -> u64 {
+        self.hand[self.played_index]
+    }
+
+    pub fn remaining(&self) -> [u64; REMAINING] {
+        assert_eq!(REMAINING + 1, HAND_SIZE, "REMAINING must be HAND_SIZE - 1");
+        std::array::from_fn(|i| {
+            if i < self.played_index {
+                self.hand[i]
+            } else {
+                self.hand[i + 1]
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct PlayCardCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize, const REMAINING: usize> Circuit<F>
+    for PlayCardCircuit<F, HAND_SIZE, REMAINING>
+{
+    type Config = PlayCardCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        assert_eq!(REMAINING + 1, HAND_SIZE, "REMAINING must be HAND_SIZE - 1");
+
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        PlayCardCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let hand_chip = HandChip::from(config.poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let mut hand = vec![];
+        for card in self.hand {
+            hand.push(gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(F::from(card)),
+            )?);
+        }
+        let hand_salt = gate_chip.load_private(
+            layouter.namespace(|| "load hand salt"),
+            Value::known(self.hand_salt),
+        )?;
+        let hand_commitment = hand_chip.commit(
+            layouter.namespace(|| "hand commitment"),
+            context.session_id.clone(),
+            hand.clone().try_into().unwrap(),
+            hand_salt,
+        )?;
+
+        for i in 0..HAND_SIZE {
+            for j in i + 1..HAND_SIZE {
+                let equal = gate_chip.is_equal(
+                    layouter.namespace(|| "cards equal"),
+                    hand[i].clone(),
+                    hand[j].clone(),
+                )?;
+                gate_chip.assert_constant(
+                    layouter.namespace(|| "distinct cards"),
+                    equal,
+                    F::ZERO,
+                )?;
+            }
+        }
+
+        let played_index = gate_chip.load_private(
+            layouter.namespace(|| "load played index"),
+            Value::known(F::from(self.played_index as u64)),
+        )?;
+        let flags = gate_chip.one_hot(layouter.namespace(|| "one hot"), played_index, HAND_SIZE)?;
+        let played_card =
+            gate_chip.inner_product(layouter.namespace(|| "played card"), &flags, &hand)?;
+
+        // cards after the played one shift down by one
+        let mut shifted = gate_chip.load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        let mut remaining = vec![];
+        for i in 0..REMAINING {
+            shifted = gate_chip.add(layouter.namespace(|| "shifted"), shifted, flags[i].clone())?;
+            remaining.push(gate_chip.select(
+                layouter.namespace(|| "remaining card"),
+                shifted.clone(),
+                hand[i + 1].clone(),
+                hand[i].clone(),
+            )?);
+        }
+        let remaining_salt = gate_chip.load_private(
+            layouter.namespace(|| "load remaining salt"),
+            Value::known(self.remaining_salt),
+        )?;
+        let remaining_commitment = hand_chip.commit(
+            layouter.namespace(|| "remaining commitment"),
+            context.session_id.clone(),
+            remaining.try_into().unwrap(),
+            remaining_salt,
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(hand_commitment.cell(), config.instance, 0)?;
+        layouter.constrain_instance(played_card.cell(), config.instance, 1)?;
+        layouter.constrain_instance(remaining_commitment.cell(), config.instance, 2)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 3)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const HAND_SIZE: usize, const REMAINING: usize> CircuitExt<F>
+    for PlayCardCircuit<F, HAND_SIZE, REMAINING>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            hand_commitment(self.context.session_id, self.hand, self.hand_salt),
+            F::from(self.played_card()),
+            hand_commitment(
+                self.context.session_id,
+                self.remaining(),
+                self.remaining_salt,
+            ),
+            self.context.digest(),
+        ]]
+    }
+}
@@ -0,0 +1,145 @@
+use crate::card::{Card, NUM_RANKS};
+
+const BASE: u64 = NUM_RANKS as u64;
+
+/// Weight of the category in a hand score, tie-break vectors are encoded below it.
+pub const CATEGORY_WEIGHT: u64 = BASE.pow(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    pub const ALL: [HandCategory; 9] = [
+        HandCategory::HighCard,
+        HandCategory::OnePair,
+        HandCategory::TwoPair,
+        HandCategory::ThreeOfAKind,
+        HandCategory::Straight,
+        HandCategory::Flush,
+        HandCategory::FullHouse,
+        HandCategory::FourOfAKind,
+        HandCategory::StraightFlush,
+    ];
+
+    pub fn from_score(score: u64) -> Self {
+        Self::ALL[(score / CATEGORY_WEIGHT) as usize]
+    }
+}
+
+/// Returns the tie-break vector encoded in a score, most significant rank first.
+pub fn tie_break(score: u64) -> [u64; 5] {
+    let tie_break = score % CATEGORY_WEIGHT;
+    std::array::from_fn(|i| tie_break / BASE.pow(4 - i as u32) % BASE)
+}
+
+/// Scores five ranks sorted in non-increasing order. A higher score is a better hand,
+/// the score is `category * CATEGORY_WEIGHT + tie_break` where the tie-break vector
+/// lists the ranks grouped by multiplicity (or the top card of a straight) in base 13.
+pub fn score_ranks(sorted_ranks: [u64; 5], flush: bool) -> u64 {
+    // (count, rank) ordered by count then rank
+    let mut groups: Vec<(u64, u64)> = vec![];
+    for rank in sorted_ranks {
+        match groups.last_mut() {
+            Some((count, last)) if *last == rank => *count += 1,
+            _ => groups.push((1, rank)),
+        }
+    }
+    groups.sort_by(|a, b| b.cmp(a));
+
+    let straight_top = if groups.len() == 5 && sorted_ranks[0] - sorted_ranks[4] == 4 {
+        Some(sorted_ranks[0])
+    } else if sorted_ranks == [12, 3, 2, 1, 0] {
+        // the wheel, ace plays low
+        Some(3)
+    } else {
+        None
+    };
+
+    let category = match (straight_top, flush, groups[0].0, groups.get(1).map(|g| g.0)) {
+        (Some(_), true, _, _) => HandCategory::StraightFlush,
+        (_, _, 4, _) => HandCategory::FourOfAKind,
+        (_, _, 3, Some(2)) => HandCategory::FullHouse,
+        (_, true, _, _) => HandCategory::Flush,
+        (Some(_), _, _, _) => HandCategory::Straight,
+        (_, _, 3, _) => HandCategory::ThreeOfAKind,
+        (_, _, 2, Some(2)) => HandCategory::TwoPair,
+        (_, _, 2, _) => HandCategory::OnePair,
+        _ => HandCategory::HighCard,
+    };
+
+    let ranks: Vec<u64> = match (category, straight_top) {
+        (HandCategory::Straight | HandCategory::StraightFlush, Some(top)) => vec![top],
+        _ => groups.iter().map(|(_, rank)| *rank).collect(),
+    };
+    let tie_break = (0..5).fold(0, |acc, i| acc * BASE + ranks.get(i).copied().unwrap_or(0));
+
+    category as u64 * CATEGORY_WEIGHT + tie_break
+}
+
+pub fn score_five(cards: [Card; 5]) -> u64 {
+    let mut ranks = cards.map(|card| card.rank);
+    ranks.sort_by(|a, b| b.cmp(a));
+    let flush = cards.iter().all(|card| card.suit == cards[0].suit);
+    score_ranks(ranks, flush)
+}
+
+/// The 21 ways to pick 5 of 7 cards.
+pub fn combinations() -> Vec<[usize; 5]> {
+    let mut combinations = vec![];
+    for i in 0..7 {
+        for j in (i + 1)..7 {
+            let mut combination = (0..7).filter(|k| *k != i && *k != j);
+            combinations.push(std::array::from_fn(|_| combination.next().unwrap()));
+        }
+    }
+    combinations
+}
+
+/// Score of the best 5 card hand out of 7 cards.
+pub fn best_of_seven(cards: [Card; 7]) -> u64 {
+    combinations()
+        .into_iter()
+        .map(|combination| score_five(combination.map(|i| cards[i])))
+        .max()
+        .unwrap()
+}
+
+/// Lookup key of five sorted ranks, the ranks in base 13 followed by the flush bit.
+pub fn rank_key(sorted_ranks: [u64; 5], flush: bool) -> u64 {
+    sorted_ranks.iter().fold(0, |acc, rank| acc * BASE + rank) * 2 + flush as u64
+}
+
+/// `(key, score)` for every possible 5 card hand. The first row is `(0, 0)` which is
+/// what disabled lookup rows resolve to, key 0 would be five twos so no hand maps to it.
+pub fn rank_table() -> Vec<(u64, u64)> {
+    let mut table = vec![(0, 0)];
+    for a in 0..BASE {
+        for b in 0..=a {
+            for c in 0..=b {
+                for d in 0..=c {
+                    for e in 0..=d {
+                        let ranks = [a, b, c, d, e];
+                        if a == e {
+                            continue;
+                        }
+                        table.push((rank_key(ranks, false), score_ranks(ranks, false)));
+                        if a > b && b > c && c > d && d > e {
+                            table.push((rank_key(ranks, true), score_ranks(ranks, true)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    table
+}
@@ -0,0 +1,312 @@
+use std::marker::PhantomData;
+
+use crate::{
+    card::NUM_RANKS,
+    card_chip::AssignedCard,
+    compare_chip::CompareChip,
+    gate_chip::GateChip,
+    poker::{combinations, rank_table, score_ranks},
+    utils::fe_to_u64,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter, Value},
+        plonk::{Advice, Column, Error, Selector, TableColumn},
+        poly::Rotation,
+    },
+    FieldExt,
+};
+
+type Cell<F> = AssignedCell<F, F>;
+
+/// Scores fit in 6 base 13 limbs, `9 * 13^5 < 13^6`.
+const SCORE_LIMBS: usize = 6;
+
+#[derive(Clone)]
+pub struct PokerConfig<F: FieldExt> {
+    q_lookup: Selector,
+    key: TableColumn,
+    score: TableColumn,
+    advice: Column<Advice>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PokerConfig<F> {
+    pub fn configure(
+        meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>,
+        advice: Option<Column<Advice>>,
+    ) -> Self {
+        let q_lookup = meta.complex_selector();
+        let key = meta.lookup_table_column();
+        let score = meta.lookup_table_column();
+        let advice = advice.unwrap_or(meta.advice_column());
+
+        meta.lookup("hand score must match rank table", |meta| {
+            let q_lookup = meta.query_selector(q_lookup);
+            let key_cell = meta.query_advice(advice, Rotation::cur());
+            let score_cell = meta.query_advice(advice, Rotation::next());
+
+            vec![
+                (q_lookup.clone() * key_cell, key),
+                (q_lookup * score_cell, score),
+            ]
+        });
+
+        PokerConfig {
+            q_lookup,
+            key,
+            score,
+            advice,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn construct(
+        &self,
+        mut layouter: impl Layouter<F>,
+        gate_chip: GateChip<F>,
+        compare_chip: CompareChip<F, NUM_RANKS>,
+    ) -> Result<PokerChip<F>, Error> {
+        layouter.namespace(|| "poker init").assign_table(
+            || "rank table",
+            |mut table| {
+                for (i, (key, score)) in rank_table().into_iter().enumerate() {
+                    table.assign_cell(|| "key", self.key, i, || Value::known(F::from(key)))?;
+                    table.assign_cell(
+                        || "score",
+                        self.score,
+                        i,
+                        || Value::known(F::from(score)),
+                    )?;
+                }
+                Ok(())
+            },
+        )?;
+
+        Ok(PokerChip {
+            config: self.clone(),
+            gate_chip,
+            compare_chip,
+        })
+    }
+}
+
+/// Evaluates poker hands with the scores of `crate::poker`.
+#[derive(Clone)]
+pub struct PokerChip<F: FieldExt> {
+    config: PokerConfig<F>,
+    gate_chip: GateChip<F>,
+    compare_chip: CompareChip<F, NUM_RANKS>,
+}
+
+impl<F: FieldExt> PokerChip<F> {
+    /// Returns `[sum(v), sum(v^2), .., sum(v^5)]`. Equal power sums mean the two sets of
+    /// five values are permutations of each other.
+    fn power_sums(
+        &self,
+        mut layouter: impl Layouter<F>,
+        values: &[Cell<F>],
+    ) -> Result<Vec<Cell<F>>, Error> {
+        let mut sums = vec![];
+        let mut powers = values.to_vec();
+        for _ in 0..values.len() {
+            let mut sum = self
+                .gate_chip
+                .load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+            for power in powers.iter() {
+                sum = self
+                    .gate_chip
+                    .add(layouter.namespace(|| "sum"), sum, power.clone())?;
+            }
+            sums.push(sum);
+
+            for (power, value) in powers.iter_mut().zip(values.iter()) {
+                *power = self.gate_chip.mul(
+                    layouter.namespace(|| "next power"),
+                    power.clone(),
+                    value.clone(),
+                )?;
+            }
+        }
+        Ok(sums)
+    }
+
+    /// Score of a five card hand.
+    pub fn score_five(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[AssignedCard<F>; 5],
+    ) -> Result<Cell<F>, Error> {
+        let sorted_values = sorted_ranks(cards);
+        let mut sorted = vec![];
+        for i in 0..5 {
+            sorted.push(self.gate_chip.load_private(
+                layouter.namespace(|| "load sorted rank"),
+                sorted_values.as_ref().map(|ranks| F::from(ranks[i])),
+            )?);
+        }
+        self.score_sorted(
+            layouter.namespace(|| "score sorted"),
+            cards,
+            &sorted.try_into().unwrap(),
+        )
+    }
+
+    /// Score of a five card hand from the prover's sorted ranks, `sorted` is constrained
+    /// to be the ranks of `cards` in non-increasing order.
+    pub fn score_sorted(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[AssignedCard<F>; 5],
+        sorted: &[Cell<F>; 5],
+    ) -> Result<Cell<F>, Error> {
+        let ranks = cards
+            .iter()
+            .map(|card| card.rank.clone())
+            .collect::<Vec<_>>();
+
+        // sorted ranks must be non-increasing and a permutation of the ranks
+        for i in 0..4 {
+            self.compare_chip.assert_lte(
+                layouter.namespace(|| "sorted"),
+                sorted[i + 1].clone(),
+                sorted[i].clone(),
+            )?;
+        }
+        let rank_sums = self.power_sums(layouter.namespace(|| "rank power sums"), &ranks)?;
+        let sorted_sums = self.power_sums(layouter.namespace(|| "sorted power sums"), sorted)?;
+        for (a, b) in rank_sums.into_iter().zip(sorted_sums) {
+            self.gate_chip
+                .assert_equal(layouter.namespace(|| "same ranks"), a, b)?;
+        }
+
+        let mut flush = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        for card in cards.iter().skip(1) {
+            let same_suit = self.gate_chip.is_equal(
+                layouter.namespace(|| "same suit"),
+                cards[0].suit.clone(),
+                card.suit.clone(),
+            )?;
+            flush = self
+                .gate_chip
+                .mul(layouter.namespace(|| "flush"), flush, same_suit)?;
+        }
+
+        let base = self.gate_chip.load_constant(
+            layouter.namespace(|| "load base"),
+            F::from(NUM_RANKS as u64),
+        )?;
+        let two = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load two"), F::from(2))?;
+        let mut key = sorted[0].clone();
+        for rank in sorted.iter().skip(1) {
+            key = self.gate_chip.addmul(
+                layouter.namespace(|| "key * base + rank"),
+                key,
+                base.clone(),
+                rank.clone(),
+            )?;
+        }
+        let key = self.gate_chip.addmul(
+            layouter.namespace(|| "key * 2 + flush"),
+            key,
+            two,
+            flush.clone(),
+        )?;
+
+        let score = sorted_ranks(cards)
+            .zip(flush.value())
+            .map(|(ranks, flush)| {
+                F::from(score_ranks(ranks.try_into().unwrap(), *flush == F::ONE))
+            });
+        layouter.assign_region(
+            || "rank lookup",
+            |mut region| {
+                self.config.q_lookup.enable(&mut region, 0)?;
+                key.copy_advice(|| "key", &mut region, self.config.advice, 0)?;
+                region.assign_advice(|| "score", self.config.advice, 1, || score)
+            },
+        )
+    }
+
+    /// Score of the best five card hand out of seven distinct cards.
+    pub fn best_of_seven(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cards: &[AssignedCard<F>; 7],
+    ) -> Result<Cell<F>, Error> {
+        for i in 0..7 {
+            for j in (i + 1)..7 {
+                let same = self.gate_chip.is_equal(
+                    layouter.namespace(|| "same card"),
+                    cards[i].index.clone(),
+                    cards[j].index.clone(),
+                )?;
+                self.gate_chip
+                    .assert_constant(layouter.namespace(|| "distinct"), same, F::ZERO)?;
+            }
+        }
+
+        let mut scores = vec![];
+        for combination in combinations() {
+            let hand = combination.map(|i| cards[i].clone());
+            scores.push(self.score_five(layouter.namespace(|| "score five"), &hand)?);
+        }
+
+        let best = self.gate_chip.load_private(
+            layouter.namespace(|| "load best"),
+            scores
+                .iter()
+                .fold(Value::known(0), |acc, score| {
+                    acc.zip(score.value())
+                        .map(|(acc, score)| acc.max(fe_to_u64(*score)))
+                })
+                .map(F::from),
+        )?;
+
+        // best is at least every score and equal to one of them
+        let mut product = self
+            .gate_chip
+            .load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        for score in scores {
+            self.compare_chip.assert_lte_limbs(
+                layouter.namespace(|| "score <= best"),
+                score.clone(),
+                best.clone(),
+                SCORE_LIMBS,
+            )?;
+            let diff =
+                self.gate_chip
+                    .sub(layouter.namespace(|| "best - score"), best.clone(), score)?;
+            product = self
+                .gate_chip
+                .mul(layouter.namespace(|| "product"), product, diff)?;
+        }
+        self.gate_chip.assert_constant(
+            layouter.namespace(|| "best is a score"),
+            product,
+            F::ZERO,
+        )?;
+
+        Ok(best)
+    }
+}
+
+/// The ranks of `cards` in non-increasing order, the hint of [`PokerChip::score_sorted`].
+fn sorted_ranks<F: FieldExt>(cards: &[AssignedCard<F>; 5]) -> Value<Vec<u64>> {
+    cards
+        .iter()
+        .fold(Value::known(vec![]), |acc, card| {
+            acc.zip(card.rank.value()).map(|(mut acc, rank)| {
+                acc.push(fe_to_u64(*rank));
+                acc
+            })
+        })
+        .map(|mut ranks| {
+            ranks.sort_by(|a, b| b.cmp(a));
+            ranks
+        })
+}
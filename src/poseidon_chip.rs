@@ -1,11 +1,21 @@
-use std::marker::PhantomData;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, Mutex, OnceLock},
+};
 
 use halo2_utils::{
     halo2_gadgets::poseidon::{
-        primitives::{self as poseidon, generate_constants, ConstantLength, Mds, Spec},
-        Hash, Pow5Chip,
+        primitives::{
+            self as poseidon, generate_constants, Absorbing, ConstantLength, Domain, Mds, Spec,
+        },
+        Hash, PaddedWord, PoseidonInstructions, Pow5Chip, Sponge, StateWord,
+    },
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::{Advice, Column, Error},
     },
-    halo2_proofs::{circuit::Layouter, plonk::Error},
     FieldExt,
 };
 
@@ -13,6 +23,191 @@ pub fn poseidon_sync<F: FieldExt, const LENGTH: usize>(input: [F; LENGTH]) -> F
     poseidon::Hash::<F, MySpec<F, 3, 2>, ConstantLength<LENGTH>, 3, 2>::init().hash(input)
 }
 
+/// Tag of a hash domain. Hashes of different domains never share a sponge state, so
+/// e.g. a key commitment cannot be passed off as a hand commitment of the same shape.
+pub trait HashDomain {
+    const TAG: u64;
+    const NAME: &'static str;
+}
+
+macro_rules! hash_domains {
+    ($($(#[$doc:meta])* $name:ident = $tag:expr,)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name;
+
+            impl HashDomain for $name {
+                const TAG: u64 = $tag;
+                const NAME: &'static str = stringify!($name);
+            }
+        )*
+    };
+}
+
+// tag zero is left to the untagged `poseidon_sync`
+hash_domains! {
+    /// Commitments to DSK keys and cut offsets, opened by `KeyOpeningCircuit`.
+    KeyCommit = 1,
+    /// `hand_chip::hand_commitment`.
+    HandCommit = 2,
+    /// `game_step::state_hash`.
+    StateHash = 3,
+    /// Inner nodes of `merkle::MerkleTree`.
+    MerkleNode = 4,
+    /// `deck_state::card_leaf`.
+    CardLeaf = 5,
+    /// `deck_state::draw_nullifier`.
+    DrawNullifier = 6,
+    /// `seating::Identity::commitment`.
+    IdentityCommit = 7,
+    /// `seating::Identity::table_nullifier`.
+    TableNullifier = 8,
+    /// `context::GameContext::digest`.
+    ContextDigest = 9,
+    /// `key_registration_circuit::registration_tag`.
+    KeyRegistration = 10,
+    /// `barnett_smart::KeyAggregator::commitment`.
+    JointKeyCommit = 11,
+    /// Deterministic nonce of `eddsa::SigningKey::sign`.
+    EddsaNonce = 12,
+    /// `signed_action_circuit::action_message`.
+    SignedAction = 13,
+    /// The cards inside `hand_chip::hand_commitment`.
+    HandCards = 14,
+    /// The state inside `game_step::state_hash`.
+    StateWords = 15,
+    /// `poseidon_cipher::shared_key`.
+    CipherKey = 16,
+    /// Sponge of `poseidon_cipher::encrypt`.
+    PoseidonCipher = 17,
+}
+
+/// Initial capacity of a `D` sponge over `length` elements, `TAG * 2^96 + length * 2^64`.
+pub fn domain_capacity<F: FieldExt, D: HashDomain>(length: usize) -> F {
+    F::from_u128(((D::TAG as u128) << 96) + ((length as u128) << 64))
+}
+
+/// Sponge domain of a `D` tagged hash of `L` elements. The capacity is initialized with
+/// [`domain_capacity`], which extends the `L * 2^64` of `ConstantLength<L>`.
+#[derive(Debug, Clone, Copy)]
+pub struct Tagged<D: HashDomain, const L: usize>(PhantomData<D>);
+
+impl<F: FieldExt, D: HashDomain, const L: usize, const RATE: usize> Domain<F, RATE>
+    for Tagged<D, L>
+{
+    type Padding = std::iter::Take<std::iter::Repeat<F>>;
+
+    fn name() -> String {
+        format!("{}<{}>", D::NAME, L)
+    }
+
+    fn initial_capacity_element() -> F {
+        domain_capacity::<F, D>(L)
+    }
+
+    fn padding(input_len: usize) -> Self::Padding {
+        assert_eq!(input_len, L);
+        let k = (L + RATE - 1) / RATE;
+        std::iter::repeat(F::ZERO).take(k * RATE - L)
+    }
+}
+
+/// The Poseidon permutation of [`MySpec`] with width 3.
+pub fn permute<F: FieldExt>(state: &mut [F; 3]) {
+    permute_spec::<F, MySpec<F, 3, 2>, 3, 2>(state)
+}
+
+type SpecConstants<F, const WIDTH: usize> = Arc<(Vec<[F; WIDTH]>, Mds<F, WIDTH>)>;
+
+/// Round constants and MDS matrix of `S`, generated on first use.
+fn spec_constants<
+    F: FieldExt,
+    S: Spec<F, WIDTH, RATE> + 'static,
+    const WIDTH: usize,
+    const RATE: usize,
+>() -> SpecConstants<F, WIDTH> {
+    static CONSTANTS: OnceLock<Mutex<HashMap<TypeId, Box<dyn Any + Send + Sync>>>> =
+        OnceLock::new();
+    let mut cache = CONSTANTS.get_or_init(Default::default).lock().unwrap();
+    cache
+        .entry(TypeId::of::<S>())
+        .or_insert_with(|| {
+            let (round_constants, mds, _) = S::constants();
+            Box::new(SpecConstants::<F, WIDTH>::new((round_constants, mds)))
+        })
+        .downcast_ref::<SpecConstants<F, WIDTH>>()
+        .unwrap()
+        .clone()
+}
+
+fn permute_spec<
+    F: FieldExt,
+    S: Spec<F, WIDTH, RATE> + 'static,
+    const WIDTH: usize,
+    const RATE: usize,
+>(
+    state: &mut [F; WIDTH],
+) {
+    let spec = spec_constants::<F, S, WIDTH, RATE>();
+    let (round_constants, mds) = spec.as_ref();
+    let half_full_rounds = S::full_rounds() / 2;
+    let partial_rounds = S::partial_rounds();
+
+    for (round, constants) in round_constants.iter().enumerate() {
+        for (word, constant) in state.iter_mut().zip(constants) {
+            *word += constant;
+        }
+        if round < half_full_rounds || round >= half_full_rounds + partial_rounds {
+            for word in state.iter_mut() {
+                *word = S::sbox(*word);
+            }
+        } else {
+            state[0] = S::sbox(state[0]);
+        }
+        let words = *state;
+        *state =
+            std::array::from_fn(|i| (0..WIDTH).fold(F::ZERO, |acc, j| acc + mds[i][j] * words[j]));
+    }
+}
+
+/// circomlib's `poseidon` of `RATE` inputs: the state `[0, inputs..]` goes through one
+/// [`CircomSpec`] permutation and the hash is its first word.
+pub fn poseidon_circom<F: FieldExt, const WIDTH: usize, const RATE: usize>(inputs: [F; RATE]) -> F {
+    assert_eq!(
+        WIDTH,
+        RATE + 1,
+        "circomlib hashes with a single permutation"
+    );
+    let mut state = [F::ZERO; WIDTH];
+    state[1..].copy_from_slice(&inputs);
+    permute_spec::<F, CircomSpec<F, WIDTH, RATE>, WIDTH, RATE>(&mut state);
+    state[0]
+}
+
+/// Hashes `input` in the domain `D`, the sponge is the one of [`poseidon_sync`] with the
+/// capacity of [`Tagged`].
+pub fn poseidon_tagged<F: FieldExt, D: HashDomain, const LENGTH: usize>(
+    _domain: D,
+    input: [F; LENGTH],
+) -> F {
+    let mut state = [
+        F::ZERO,
+        F::ZERO,
+        <Tagged<D, LENGTH> as Domain<F, 2>>::initial_capacity_element(),
+    ];
+    let padded = input
+        .into_iter()
+        .chain(<Tagged<D, LENGTH> as Domain<F, 2>>::padding(LENGTH))
+        .collect::<Vec<_>>();
+    for chunk in padded.chunks(2) {
+        state[0] += chunk[0];
+        state[1] += chunk[1];
+        permute(&mut state);
+    }
+    state[0]
+}
+
 #[derive(Clone)]
 pub struct PoseidonChip<F: FieldExt, const LENGTH: usize> {
     pub chip: Pow5Chip<F, 3, 2>,
@@ -47,6 +242,146 @@ impl<F: FieldExt, const LENGTH: usize> PoseidonChip<F, LENGTH> {
             layouter.namespace(|| "poseidon init"),
         )
     }
+
+    /// Hashes `L` cells, the in-circuit counterpart of [`poseidon_sync`].
+    pub fn hash<const L: usize>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        inputs: [AssignedCell<F, F>; L],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let hasher = Hash::<_, _, MySpec<F, 3, 2>, ConstantLength<L>, 3, 2>::init(
+            self.chip.clone(),
+            layouter.namespace(|| "poseidon init"),
+        )?;
+        hasher.hash(layouter.namespace(|| "poseidon hash"), inputs)
+    }
+
+    /// The Poseidon permutation of `state`, the in-circuit counterpart of [`permute`].
+    pub fn permute(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: [AssignedCell<F, F>; 3],
+    ) -> Result<[AssignedCell<F, F>; 3], Error> {
+        let state = <Pow5Chip<F, 3, 2> as PoseidonInstructions<F, MySpec<F, 3, 2>, 3, 2>>::permute(
+            &self.chip,
+            &mut layouter,
+            &state.map(StateWord::from),
+        )?;
+        Ok(state.map(AssignedCell::from))
+    }
+
+    /// Hashes `L` cells in the domain `D`, the in-circuit counterpart of
+    /// [`poseidon_tagged`].
+    pub fn hash_tagged<D: HashDomain, const L: usize>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        _domain: D,
+        inputs: [AssignedCell<F, F>; L],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let mut sponge =
+            Sponge::<_, _, MySpec<F, 3, 2>, Absorbing<PaddedWord<F>, 2>, Tagged<D, L>, 3, 2>::new(
+                self.chip.clone(),
+                layouter.namespace(|| "poseidon init"),
+            )?;
+        let words = inputs
+            .into_iter()
+            .map(PaddedWord::Message)
+            .chain(<Tagged<D, L> as Domain<F, 2>>::padding(L).map(PaddedWord::Padding));
+        for (i, word) in words.enumerate() {
+            sponge.absorb(layouter.namespace(|| format!("absorb {i}")), word)?;
+        }
+        sponge
+            .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+            .squeeze(layouter.namespace(|| "squeeze"))
+    }
+}
+
+/// In-circuit counterpart of [`poseidon_circom`], a `Pow5Chip` of its own width.
+#[derive(Clone)]
+pub struct CircomPoseidonChip<F: FieldExt, const WIDTH: usize, const RATE: usize> {
+    chip: Pow5Chip<F, WIDTH, RATE>,
+    advice: Column<Advice>,
+}
+
+impl<F: FieldExt, const WIDTH: usize, const RATE: usize> CircomPoseidonChip<F, WIDTH, RATE> {
+    pub fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self {
+        let state = (0..WIDTH).map(|_| meta.advice_column()).collect::<Vec<_>>();
+        let partial_sbox = meta.advice_column();
+        let rc_a = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        let rc_b = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+        meta.enable_constant(rc_b[0]);
+
+        let advice = state[0];
+        let config = Pow5Chip::<F, WIDTH, RATE>::configure::<CircomSpec<F, WIDTH, RATE>>(
+            meta,
+            state.try_into().unwrap(),
+            partial_sbox,
+            rc_a.try_into().unwrap(),
+            rc_b.try_into().unwrap(),
+        );
+        Self {
+            chip: Pow5Chip::<F, WIDTH, RATE>::construct(config),
+            advice,
+        }
+    }
+
+    /// Hashes `RATE` cells like circomlib's `poseidon`.
+    pub fn hash(
+        &self,
+        mut layouter: impl Layouter<F>,
+        inputs: [AssignedCell<F, F>; RATE],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let zero = layouter.assign_region(
+            || "load zero",
+            |mut region| region.assign_advice_from_constant(|| "zero", self.advice, 0, F::ZERO),
+        )?;
+        let state = std::iter::once(zero)
+            .chain(inputs)
+            .map(StateWord::from)
+            .collect::<Vec<_>>();
+        let state = <Pow5Chip<F, WIDTH, RATE> as PoseidonInstructions<
+            F,
+            CircomSpec<F, WIDTH, RATE>,
+            WIDTH,
+            RATE,
+        >>::permute(&self.chip, &mut layouter, &state.try_into().unwrap())?;
+        let [hash, ..] = state.map(AssignedCell::from);
+        Ok(hash)
+    }
+}
+
+/// Partial rounds of circomlib's Poseidon for the widths 2 to 17.
+const CIRCOM_PARTIAL_ROUNDS: [usize; 16] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+/// The parameters of circomlib's Poseidon, its constants are the ones of the reference
+/// Grain generator.
+#[derive(Debug, Clone, Copy)]
+pub struct CircomSpec<F: FieldExt, const WIDTH: usize, const RATE: usize>(PhantomData<F>);
+
+impl<F: FieldExt, const WIDTH: usize, const RATE: usize> Spec<F, WIDTH, RATE>
+    for CircomSpec<F, WIDTH, RATE>
+{
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        CIRCOM_PARTIAL_ROUNDS[WIDTH - 2]
+    }
+
+    fn sbox(val: F) -> F {
+        val.pow_vartime([5])
+    }
+
+    fn secure_mds() -> usize {
+        0
+    }
+
+    fn constants() -> (Vec<[F; WIDTH]>, Mds<F, WIDTH>, Mds<F, WIDTH>) {
+        generate_constants::<_, Self, WIDTH, RATE>()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
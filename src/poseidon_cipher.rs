@@ -0,0 +1,64 @@
+use crate::{
+    babyjubjub::Point,
+    poseidon_chip::{domain_capacity, permute, poseidon_tagged, CipherKey, PoseidonCipher},
+};
+use halo2_utils::FieldExt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CipherError {
+    /// The ciphertext does not even hold the authentication tag.
+    MissingTag,
+    /// Wrong key or nonce, or the ciphertext was tampered with.
+    InvalidTag,
+}
+
+/// Key shared by the holders of `secret_key` and of the secret of `public_key`,
+/// `Poseidon(x, y)` of the Diffie-Hellman point in the [`CipherKey`] domain.
+pub fn shared_key<F: FieldExt>(secret_key: F, public_key: Point<F>) -> F {
+    let shared = public_key.mul(secret_key);
+    poseidon_tagged(CipherKey, [shared.x, shared.y])
+}
+
+/// The key and nonce are absorbed first, the capacity holds the message length.
+fn initial_state<F: FieldExt>(key: F, nonce: F, length: usize) -> [F; 3] {
+    let mut state = [key, nonce, domain_capacity::<F, PoseidonCipher>(length)];
+    permute(&mut state);
+    state
+}
+
+/// Encrypts `message` with a Poseidon sponge in duplex mode, every ciphertext element is
+/// the rate word after its message element was added. The last element is the
+/// authentication tag.
+///
+/// The same nonce must never be used twice with a key.
+pub fn encrypt<F: FieldExt>(key: F, nonce: F, message: &[F]) -> Vec<F> {
+    let mut state = initial_state(key, nonce, message.len());
+    let mut ciphertext = vec![];
+    for chunk in message.chunks(2) {
+        for (word, value) in state.iter_mut().zip(chunk) {
+            *word += value;
+            ciphertext.push(*word);
+        }
+        permute(&mut state);
+    }
+    ciphertext.push(state[0]);
+    ciphertext
+}
+
+/// Inverse of [`encrypt`], fails unless the tag matches.
+pub fn decrypt<F: FieldExt>(key: F, nonce: F, ciphertext: &[F]) -> Result<Vec<F>, CipherError> {
+    let (tag, ciphertext) = ciphertext.split_last().ok_or(CipherError::MissingTag)?;
+    let mut state = initial_state(key, nonce, ciphertext.len());
+    let mut message = vec![];
+    for chunk in ciphertext.chunks(2) {
+        for (word, value) in state.iter_mut().zip(chunk) {
+            message.push(*value - *word);
+            *word = *value;
+        }
+        permute(&mut state);
+    }
+    if state[0] != *tag {
+        return Err(CipherError::InvalidTag);
+    }
+    Ok(message)
+}
@@ -1,4 +1,8 @@
-use crate::poseidon_chip::{poseidon_sync, PoseidonChip};
+use crate::{
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    poseidon_chip::{poseidon_sync, PoseidonChip},
+};
 use halo2_utils::{
     halo2_proofs::{
         circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
@@ -7,16 +11,19 @@ use halo2_utils::{
     CircuitExt, FieldExt,
 };
 
+/// Instances are `[Poseidon(a, b), context_digest]`.
 #[derive(Clone)]
 pub struct PreimageCircuit<F: FieldExt, const N: usize> {
     pub a: F,
     pub b: F,
+    pub context: GameContext<F>,
 }
 
 #[derive(Clone)]
 pub struct PreimageCircuitConfig<F: FieldExt, const N: usize> {
     advice: Column<Advice>,
     instance: Column<Instance>,
+    gate_chip: GateChip<F>,
     poseidon_chip: PoseidonChip<F, 2>,
 }
 
@@ -48,10 +55,12 @@ impl<F: FieldExt, const N: usize> Circuit<F> for PreimageCircuit<F, N> {
         let poseidon_chip = PoseidonChip::configure(meta);
         meta.enable_equality(advice);
         meta.enable_equality(instance);
+        let gate_chip = GateChip::configure(meta, Some(advice));
 
         PreimageCircuitConfig {
             advice,
             instance,
+            gate_chip,
             poseidon_chip,
         }
     }
@@ -67,8 +76,13 @@ impl<F: FieldExt, const N: usize> Circuit<F> for PreimageCircuit<F, N> {
             .poseidon_chip
             .construct(layouter.namespace(|| "poseidon"))?;
         let final_hash = poseidon.hash(layouter.namespace(|| "hash"), [a, b])?;
+        let context_chip =
+            ContextChip::from(config.gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
 
         layouter.constrain_instance(final_hash.cell(), config.instance, 0)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 1)?;
         Ok(())
     }
 }
@@ -79,6 +93,6 @@ impl<F: FieldExt, const N: usize> CircuitExt<F> for PreimageCircuit<F, N> {
     // }
 
     fn instances(&self) -> Vec<Vec<F>> {
-        vec![vec![poseidon_sync([self.a, self.b])]]
+        vec![vec![poseidon_sync([self.a, self.b]), self.context.digest()]]
     }
 }
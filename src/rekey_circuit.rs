@@ -0,0 +1,208 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    deck_chip::DeckChip,
+    distinct_single_key::{decrypt_cards, encrypt_cards, key_commitment, pack_cards},
+    gate_chip::GateChip,
+    poseidon_chip::{KeyCommit, PoseidonChip},
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Re-keys a DSK encrypted deck: proves that the deck encrypted under the old key is
+/// the same deck encrypted under the new key, without revealing either key.
+///
+/// Decks are packed like DSK, instances are `[old slots.., new slots..,
+/// old_key_commitment, new_key_commitment, context_digest]`.
+#[derive(Clone)]
+pub struct RekeyCircuit<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+> {
+    pub encrypted_cards: [u64; NUM_CARDS],
+    pub old_key: u64,
+    pub old_key_salt: F,
+    pub new_key: u64,
+    pub new_key_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    RekeyCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    pub fn raw_cards(&self) -> [u64; NUM_CARDS] {
+        decrypt_cards::<NUM_CARDS>(&self.encrypted_cards, self.old_key)
+            .try_into()
+            .unwrap()
+    }
+
+    pub fn rekeyed_cards(&self) -> [u64; NUM_CARDS] {
+        encrypt_cards(self.raw_cards(), self.new_key)
+    }
+}
+
+#[derive(Clone)]
+pub struct RekeyCircuitConfig<F: FieldExt, const NUM_CARDS: usize> {
+    gate_chip: GateChip<F>,
+    range_config: RangeConfig<F, NUM_CARDS>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    Circuit<F> for RekeyCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    type Config = RekeyCircuitConfig<F, NUM_CARDS>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let range_config = RangeConfig::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        RekeyCircuitConfig {
+            gate_chip,
+            range_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let deck_chip =
+            DeckChip::<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>::from(gate_chip.clone(), range_chip);
+
+        let mut old_slots = vec![];
+        for slot in pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.encrypted_cards) {
+            old_slots.push(
+                gate_chip
+                    .load_private(layouter.namespace(|| "load old slot"), Value::known(slot))?,
+            );
+        }
+        let old_cards = deck_chip.unpack_deck(layouter.namespace(|| "unpack old"), &old_slots)?;
+
+        let old_key = gate_chip.load_private(
+            layouter.namespace(|| "load old key"),
+            Value::known(F::from(self.old_key)),
+        )?;
+        let new_key = gate_chip.load_private(
+            layouter.namespace(|| "load new key"),
+            Value::known(F::from(self.new_key)),
+        )?;
+
+        // the same raw card is encrypted under both keys
+        let mut new_cards = vec![];
+        for ((old_card, raw_card), new_card) in old_cards
+            .into_iter()
+            .zip(self.raw_cards())
+            .zip(self.rekeyed_cards())
+        {
+            let raw_card = gate_chip.load_private(
+                layouter.namespace(|| "load raw card"),
+                Value::known(F::from(raw_card)),
+            )?;
+            let new_card = gate_chip.load_private(
+                layouter.namespace(|| "load new card"),
+                Value::known(F::from(new_card)),
+            )?;
+            deck_chip.assert_encryption(
+                layouter.namespace(|| "old encryption"),
+                raw_card.clone(),
+                old_key.clone(),
+                old_card,
+            )?;
+            deck_chip.assert_encryption(
+                layouter.namespace(|| "new encryption"),
+                raw_card,
+                new_key.clone(),
+                new_card.clone(),
+            )?;
+            new_cards.push(new_card);
+        }
+        let new_slots = deck_chip.pack_deck(layouter.namespace(|| "pack new"), &new_cards)?;
+
+        let old_key_salt = gate_chip.load_private(
+            layouter.namespace(|| "load old key salt"),
+            Value::known(self.old_key_salt),
+        )?;
+        let old_key_commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "old key commitment"),
+            KeyCommit,
+            [context.session_id.clone(), old_key, old_key_salt],
+        )?;
+        let new_key_salt = gate_chip.load_private(
+            layouter.namespace(|| "load new key salt"),
+            Value::known(self.new_key_salt),
+        )?;
+        let new_key_commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "new key commitment"),
+            KeyCommit,
+            [context.session_id.clone(), new_key, new_key_salt],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        let num_slots = old_slots.len();
+        for (i, slot) in old_slots.iter().chain(new_slots.iter()).enumerate() {
+            layouter.constrain_instance(slot.cell(), config.instance, i)?;
+        }
+        layouter.constrain_instance(old_key_commitment.cell(), config.instance, 2 * num_slots)?;
+        layouter.constrain_instance(
+            new_key_commitment.cell(),
+            config.instance,
+            2 * num_slots + 1,
+        )?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 2 * num_slots + 2)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    CircuitExt<F> for RekeyCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        let mut values = pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.encrypted_cards);
+        values.extend(pack_cards::<F, WORD_BYTES, FIELD_BYTES>(
+            &self.rekeyed_cards(),
+        ));
+        values.push(key_commitment(
+            self.context.session_id,
+            self.old_key,
+            self.old_key_salt,
+        ));
+        values.push(key_commitment(
+            self.context.session_id,
+            self.new_key,
+            self.new_key_salt,
+        ));
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
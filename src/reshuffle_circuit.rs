@@ -0,0 +1,144 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    permutation_chip::PermutationConfig,
+    poseidon_chip::PoseidonChip,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Re-shuffles an already shuffled private deck, proving that the new deck is a
+/// permutation of the old one. Both decks are committed the way hands are, see
+/// `hand_chip::hand_commitment`, and the permutation stays private.
+///
+/// Instances are `[deck_commitment, reshuffled_commitment, context_digest]`.
+#[derive(Clone)]
+pub struct ReshuffleCircuit<F: FieldExt, const NUM_CARDS: usize> {
+    pub deck: [u64; NUM_CARDS],
+    pub deck_salt: F,
+    /// The reshuffled deck holds `deck[permutation[i]]` at position `i`.
+    pub permutation: [usize; NUM_CARDS],
+    pub reshuffled_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize> ReshuffleCircuit<F, NUM_CARDS> {
+    pub fn reshuffled(&self) -> [u64; NUM_CARDS] {
+        self.permutation.map(|i| self.deck[i])
+    }
+}
+
+#[derive(Clone)]
+pub struct ReshuffleCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    permutation_config: PermutationConfig<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize> Circuit<F> for ReshuffleCircuit<F, NUM_CARDS> {
+    type Config = ReshuffleCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let permutation_config = PermutationConfig::configure(meta);
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        ReshuffleCircuitConfig {
+            gate_chip,
+            permutation_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let permutation_chip = config.permutation_config.construct();
+        let hand_chip = HandChip::from(config.poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let mut deck = vec![];
+        for card in self.deck {
+            deck.push(gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(F::from(card)),
+            )?);
+        }
+        let mut reshuffled = vec![];
+        for card in self.reshuffled() {
+            reshuffled.push(gate_chip.load_private(
+                layouter.namespace(|| "load reshuffled card"),
+                Value::known(F::from(card)),
+            )?);
+        }
+        permutation_chip.assert_permutation(
+            layouter.namespace(|| "permutation"),
+            &deck,
+            &reshuffled,
+        )?;
+
+        let deck_salt = gate_chip.load_private(
+            layouter.namespace(|| "load deck salt"),
+            Value::known(self.deck_salt),
+        )?;
+        let deck_commitment = hand_chip.commit(
+            layouter.namespace(|| "deck commitment"),
+            context.session_id.clone(),
+            deck.try_into().unwrap(),
+            deck_salt,
+        )?;
+        let reshuffled_salt = gate_chip.load_private(
+            layouter.namespace(|| "load reshuffled salt"),
+            Value::known(self.reshuffled_salt),
+        )?;
+        let reshuffled_commitment = hand_chip.commit(
+            layouter.namespace(|| "reshuffled commitment"),
+            context.session_id.clone(),
+            reshuffled.try_into().unwrap(),
+            reshuffled_salt,
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(deck_commitment.cell(), config.instance, 0)?;
+        layouter.constrain_instance(reshuffled_commitment.cell(), config.instance, 1)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 2)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize> CircuitExt<F> for ReshuffleCircuit<F, NUM_CARDS> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            hand_commitment(self.context.session_id, self.deck, self.deck_salt),
+            hand_commitment(
+                self.context.session_id,
+                self.reshuffled(),
+                self.reshuffled_salt,
+            ),
+            self.context.digest(),
+        ]]
+    }
+}
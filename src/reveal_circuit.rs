@@ -0,0 +1,192 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    deck_chip::DeckChip,
+    distinct_single_key::{decrypt_card, key_commitment, unpack_slot},
+    gate_chip::GateChip,
+    poseidon_chip::{KeyCommit, PoseidonChip},
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Reveals a single card of a deck published by `DistinctSingleKeyCircuit` without
+/// revealing the key.
+///
+/// Instances are `[slot, index, key_commitment, card, context_digest]` where `index` is
+/// the deck position of the card and `slot` is the packed slot `index / FIELD_BYTES` of
+/// the DSK instances. The slot number and the offset `index % FIELD_BYTES` inside it are
+/// derived in-circuit, and `index` is bounded by `NUM_CARDS` so the zero padding of the
+/// last slot cannot be revealed.
+#[derive(Clone)]
+pub struct RevealCardCircuit<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+> {
+    pub slot: F,
+    pub index: usize,
+    pub key: u64,
+    pub key_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    RevealCardCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    pub fn card(&self) -> u64 {
+        let encrypted =
+            unpack_slot::<F, WORD_BYTES, FIELD_BYTES>(self.slot)[self.index % FIELD_BYTES];
+        decrypt_card::<NUM_CARDS>(encrypted, self.key)
+    }
+}
+
+#[derive(Clone)]
+pub struct RevealCardCircuitConfig<F: FieldExt, const NUM_CARDS: usize> {
+    gate_chip: GateChip<F>,
+    range_config: RangeConfig<F, NUM_CARDS>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    Circuit<F> for RevealCardCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    type Config = RevealCardCircuitConfig<F, NUM_CARDS>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let range_config = RangeConfig::<F, NUM_CARDS>::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        RevealCardCircuitConfig {
+            gate_chip,
+            range_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let deck_chip = DeckChip::<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>::from(
+            gate_chip.clone(),
+            range_chip.clone(),
+        );
+
+        let slot =
+            gate_chip.load_private(layouter.namespace(|| "load slot"), Value::known(self.slot))?;
+        let index = gate_chip.load_private(
+            layouter.namespace(|| "load index"),
+            Value::known(F::from(self.index as u64)),
+        )?;
+        range_chip.range_constrain(layouter.namespace(|| "index < NUM_CARDS"), index.clone())?;
+
+        // index = slot_index * FIELD_BYTES + offset, the one-hot flags bound both parts so
+        // the split is unique
+        let slot_index = gate_chip.load_private(
+            layouter.namespace(|| "load slot index"),
+            Value::known(F::from((self.index / FIELD_BYTES) as u64)),
+        )?;
+        let num_slots = NUM_CARDS * WORD_BYTES / FIELD_BYTES + 1;
+        gate_chip.one_hot(
+            layouter.namespace(|| "slot index flags"),
+            slot_index.clone(),
+            num_slots,
+        )?;
+        let offset = gate_chip.load_private(
+            layouter.namespace(|| "load offset"),
+            Value::known(F::from((self.index % FIELD_BYTES) as u64)),
+        )?;
+        let field_bytes = gate_chip.load_constant(
+            layouter.namespace(|| "load field bytes"),
+            F::from(FIELD_BYTES as u64),
+        )?;
+        let recomposed = gate_chip.addmul(
+            layouter.namespace(|| "slot index * FIELD_BYTES + offset"),
+            slot_index,
+            field_bytes,
+            offset.clone(),
+        )?;
+        gate_chip.assert_equal(
+            layouter.namespace(|| "index split"),
+            recomposed,
+            index.clone(),
+        )?;
+        let key = gate_chip.load_private(
+            layouter.namespace(|| "load key"),
+            Value::known(F::from(self.key)),
+        )?;
+        let key_salt = gate_chip.load_private(
+            layouter.namespace(|| "load key salt"),
+            Value::known(self.key_salt),
+        )?;
+        let card = gate_chip.load_private(
+            layouter.namespace(|| "load card"),
+            Value::known(F::from(self.card())),
+        )?;
+
+        let words = deck_chip.unpack_slot(layouter.namespace(|| "unpack slot"), slot.clone())?;
+        let encrypted = deck_chip.select(layouter.namespace(|| "select"), &words, offset)?;
+        deck_chip.assert_encryption(
+            layouter.namespace(|| "encryption"),
+            card.clone(),
+            key.clone(),
+            encrypted,
+        )?;
+
+        let commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "key commitment"),
+            KeyCommit,
+            [context.session_id.clone(), key, key_salt],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(slot.cell(), config.instance, 0)?;
+        layouter.constrain_instance(index.cell(), config.instance, 1)?;
+        layouter.constrain_instance(commitment.cell(), config.instance, 2)?;
+        layouter.constrain_instance(card.cell(), config.instance, 3)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 4)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    CircuitExt<F> for RevealCardCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            self.slot,
+            F::from(self.index as u64),
+            key_commitment(self.context.session_id, self.key, self.key_salt),
+            F::from(self.card()),
+            self.context.digest(),
+        ]]
+    }
+}
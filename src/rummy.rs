@@ -0,0 +1,102 @@
+use crate::card::{Card, DeckSpec, Rank, NUM_RANKS};
+
+/// Rummy is played with the jokers of [`DeckSpec::WITH_JOKERS`] as wildcards.
+pub const DECK: DeckSpec = DeckSpec::WITH_JOKERS;
+
+/// Smallest number of cards in a meld.
+pub const MIN_MELD_SIZE: usize = 3;
+
+/// Positions a run can occupy, the ace plays low at position 0 or high at position 13.
+pub const RUN_POSITIONS: u64 = NUM_RANKS as u64 + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeldKind {
+    /// Cards of the same rank and distinct suits.
+    Set,
+    /// Cards of consecutive ranks and the same suit.
+    Run,
+}
+
+/// Points of a card, the ace counts as 1 and faces as 10.
+pub fn card_points(rank: u64) -> u64 {
+    match rank {
+        rank if rank == Rank::Ace as u64 => 1,
+        rank if rank >= Rank::Jack as u64 => 10,
+        rank => rank + 2,
+    }
+}
+
+/// Rank of the card at run position `position`.
+pub fn run_rank(position: u64) -> u64 {
+    if position == 0 {
+        Rank::Ace as u64
+    } else {
+        position - 1
+    }
+}
+
+fn naturals(cards: &[Card]) -> Vec<(usize, Card)> {
+    cards
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, card)| !DECK.is_joker(*card))
+        .collect()
+}
+
+/// Position of the first card of a run, `None` if the natural cards do not fit a run.
+pub fn run_start(cards: &[Card]) -> Option<u64> {
+    let naturals = naturals(cards);
+    (0..=RUN_POSITIONS.checked_sub(cards.len() as u64)?).find(|start| {
+        naturals
+            .iter()
+            .all(|(i, card)| run_rank(start + *i as u64) == card.rank)
+    })
+}
+
+/// Returns the rank every card of a meld stands for, wildcards take the rank of their
+/// place in the meld. Cards of a run are listed in order, no card appears twice and at
+/// least one card must not be a wildcard. `None` if the cards do not form a meld of the
+/// given kind.
+pub fn meld_ranks(kind: MeldKind, cards: &[Card]) -> Option<Vec<u64>> {
+    if cards.len() < MIN_MELD_SIZE {
+        return None;
+    }
+    for (i, card) in cards.iter().enumerate() {
+        if cards[..i].contains(card) {
+            return None;
+        }
+    }
+    let naturals = naturals(cards);
+    let (_, first) = *naturals.first()?;
+
+    let ranks = match kind {
+        MeldKind::Set => {
+            for (i, (_, a)) in naturals.iter().enumerate() {
+                if naturals[..i].iter().any(|(_, b)| a.suit == b.suit) {
+                    return None;
+                }
+            }
+            vec![first.rank; cards.len()]
+        }
+        MeldKind::Run => {
+            if naturals.iter().any(|(_, card)| card.suit != first.suit) {
+                return None;
+            }
+            let start = run_start(cards)?;
+            (0..cards.len() as u64)
+                .map(|i| run_rank(start + i))
+                .collect()
+        }
+    };
+
+    naturals
+        .iter()
+        .all(|(i, card)| ranks[*i] == card.rank)
+        .then_some(ranks)
+}
+
+/// Total points of a meld, wildcards score as the card they stand for.
+pub fn meld_score(kind: MeldKind, cards: &[Card]) -> Option<u64> {
+    Some(meld_ranks(kind, cards)?.into_iter().map(card_points).sum())
+}
@@ -0,0 +1,346 @@
+use crate::{
+    card::{Card, Rank, NUM_RANKS, NUM_SUITS},
+    card_chip::CardConfig,
+    compare_chip::CompareChip,
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    poseidon_chip::PoseidonChip,
+    range_chip::RangeConfig,
+    rummy::{meld_ranks, meld_score, run_start, MeldKind, DECK, MIN_MELD_SIZE, RUN_POSITIONS},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Bounds comparisons, covers the indices of the joker deck and every rank.
+const RANGE: usize = 64;
+
+/// Proves that a committed group of cards forms a valid rummy meld and exposes only its
+/// score. Cards are indices of [`DECK`] in meld order, jokers are wildcards standing for
+/// the card of their place in the meld. The kind of meld stays private.
+///
+/// Instances are `[meld_commitment, score, context_digest]`.
+#[derive(Clone)]
+pub struct MeldCircuit<F: FieldExt, const MELD_SIZE: usize> {
+    pub meld: [u64; MELD_SIZE],
+    pub meld_salt: F,
+    pub kind: MeldKind,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const MELD_SIZE: usize> MeldCircuit<F, MELD_SIZE> {
+    fn cards(&self) -> [Card; MELD_SIZE] {
+        self.meld.map(|index| DECK.card(index).unwrap())
+    }
+
+    pub fn score(&self) -> u64 {
+        meld_score(self.kind, &self.cards()).expect("cards must form a meld")
+    }
+
+    /// The rank of a set or the start position of a run.
+    fn base(&self) -> u64 {
+        let cards = self.cards();
+        match self.kind {
+            MeldKind::Set => meld_ranks(self.kind, &cards).map_or(0, |ranks| ranks[0]),
+            MeldKind::Run => run_start(&cards).unwrap_or(0),
+        }
+    }
+
+    fn run_suit(&self) -> u64 {
+        self.cards()
+            .into_iter()
+            .find(|card| !DECK.is_joker(*card))
+            .map_or(0, |card| card.suit)
+    }
+}
+
+#[derive(Clone)]
+pub struct MeldCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    card_config: CardConfig<F, { NUM_SUITS + 1 }, NUM_RANKS>,
+    range_config: RangeConfig<F, RANGE>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const MELD_SIZE: usize> Circuit<F> for MeldCircuit<F, MELD_SIZE> {
+    type Config = MeldCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        assert!(
+            (MIN_MELD_SIZE as u64..=RUN_POSITIONS).contains(&(MELD_SIZE as u64)),
+            "invalid meld size"
+        );
+
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let card_config = CardConfig::configure(meta, Some(advice));
+        let range_config = RangeConfig::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        MeldCircuitConfig {
+            gate_chip,
+            card_config,
+            range_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let card_chip = config
+            .card_config
+            .construct(layouter.namespace(|| "card chip"), gate_chip.clone())?;
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let compare_chip = CompareChip::from(gate_chip.clone(), range_chip);
+        let hand_chip = HandChip::from(config.poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let zero = gate_chip.load_constant(layouter.namespace(|| "load zero"), F::ZERO)?;
+        let one = gate_chip.load_constant(layouter.namespace(|| "load one"), F::ONE)?;
+        let two = gate_chip.load_constant(layouter.namespace(|| "load two"), F::from(2))?;
+        let ace = gate_chip
+            .load_constant(layouter.namespace(|| "load ace"), F::from(Rank::Ace as u64))?;
+        let ten = gate_chip
+            .load_constant(layouter.namespace(|| "load ten"), F::from(Rank::Ten as u64))?;
+        let ten_points = gate_chip.load_constant(layouter.namespace(|| "load 10"), F::from(10))?;
+        let joker_suit = gate_chip.load_constant(
+            layouter.namespace(|| "load joker suit"),
+            F::from(DECK.joker_suit()),
+        )?;
+        let deck_size = gate_chip.load_constant(
+            layouter.namespace(|| "load deck size"),
+            F::from(DECK.size()),
+        )?;
+
+        let is_run = gate_chip.load_private(
+            layouter.namespace(|| "load is run"),
+            Value::known(F::from((self.kind == MeldKind::Run) as u64)),
+        )?;
+        gate_chip.assert_bool(layouter.namespace(|| "is run bool"), is_run.clone())?;
+        let base = gate_chip.load_private(
+            layouter.namespace(|| "load base"),
+            Value::known(F::from(self.base())),
+        )?;
+        let run_suit = gate_chip.load_private(
+            layouter.namespace(|| "load run suit"),
+            Value::known(F::from(self.run_suit())),
+        )?;
+
+        // a set rank is at most the ace, a run must end at or before the high ace
+        let max_set_rank = gate_chip.load_constant(
+            layouter.namespace(|| "load max set rank"),
+            F::from(Rank::Ace as u64),
+        )?;
+        let max_run_start = gate_chip.load_constant(
+            layouter.namespace(|| "load max run start"),
+            F::from(RUN_POSITIONS - MELD_SIZE as u64),
+        )?;
+        let max_base = gate_chip.select(
+            layouter.namespace(|| "max base"),
+            is_run.clone(),
+            max_run_start,
+            max_set_rank,
+        )?;
+        compare_chip.assert_lte(
+            layouter.namespace(|| "base not negative"),
+            zero.clone(),
+            base.clone(),
+        )?;
+        compare_chip.assert_lte(layouter.namespace(|| "base range"), base.clone(), max_base)?;
+
+        let mut meld = vec![];
+        let mut cards = vec![];
+        let mut naturals = vec![];
+        for index in self.meld {
+            let index = gate_chip.load_private(
+                layouter.namespace(|| "load card"),
+                Value::known(F::from(index)),
+            )?;
+            compare_chip.assert_lt(
+                layouter.namespace(|| "card in deck"),
+                index.clone(),
+                deck_size.clone(),
+            )?;
+            // a card, wildcards included, can only be played once per meld
+            for other in meld.iter() {
+                let repeated = gate_chip.is_equal(
+                    layouter.namespace(|| "repeated card"),
+                    index.clone(),
+                    other.clone(),
+                )?;
+                gate_chip.assert_constant(
+                    layouter.namespace(|| "distinct cards"),
+                    repeated,
+                    F::ZERO,
+                )?;
+            }
+            let card = card_chip.decompose(layouter.namespace(|| "decompose"), index.clone())?;
+            let wild = gate_chip.is_equal(
+                layouter.namespace(|| "is wild"),
+                card.suit.clone(),
+                joker_suit.clone(),
+            )?;
+            naturals.push(gate_chip.sub(layouter.namespace(|| "is natural"), one.clone(), wild)?);
+            meld.push(index);
+            cards.push(card);
+        }
+
+        let meld_salt = gate_chip.load_private(
+            layouter.namespace(|| "load meld salt"),
+            Value::known(self.meld_salt),
+        )?;
+        let meld_commitment = hand_chip.commit(
+            layouter.namespace(|| "meld commitment"),
+            context.session_id.clone(),
+            meld.try_into().unwrap(),
+            meld_salt,
+        )?;
+
+        let mut natural_count = zero.clone();
+        for natural in naturals.iter() {
+            natural_count = gate_chip.add(
+                layouter.namespace(|| "natural count"),
+                natural_count,
+                natural.clone(),
+            )?;
+        }
+        let all_wild = gate_chip.is_zero(layouter.namespace(|| "all wild"), natural_count)?;
+        gate_chip.assert_constant(layouter.namespace(|| "has natural"), all_wild, F::ZERO)?;
+
+        let mut score = zero.clone();
+        for (i, (card, natural)) in cards.iter().zip(naturals.iter()).enumerate() {
+            // rank the card stands for, run position 0 is the low ace
+            let position = gate_chip.add(
+                layouter.namespace(|| "run position"),
+                base.clone(),
+                gate_chip.load_constant(layouter.namespace(|| "load i"), F::from(i as u64))?,
+            )?;
+            let low_ace = gate_chip.is_zero(layouter.namespace(|| "low ace"), position.clone())?;
+            let below =
+                gate_chip.sub(layouter.namespace(|| "position - 1"), position, one.clone())?;
+            let run_rank = gate_chip.select(
+                layouter.namespace(|| "run rank"),
+                low_ace,
+                ace.clone(),
+                below,
+            )?;
+            let rank = gate_chip.select(
+                layouter.namespace(|| "meld rank"),
+                is_run.clone(),
+                run_rank,
+                base.clone(),
+            )?;
+
+            // natural cards must be the card they stand for
+            let rank_diff = gate_chip.sub(
+                layouter.namespace(|| "rank diff"),
+                card.rank.clone(),
+                rank.clone(),
+            )?;
+            let rank_diff = gate_chip.mul(
+                layouter.namespace(|| "natural rank diff"),
+                rank_diff,
+                natural.clone(),
+            )?;
+            gate_chip.assert_constant(layouter.namespace(|| "same rank"), rank_diff, F::ZERO)?;
+
+            let suit_diff = gate_chip.sub(
+                layouter.namespace(|| "suit diff"),
+                card.suit.clone(),
+                run_suit.clone(),
+            )?;
+            let suit_diff = gate_chip.mul(
+                layouter.namespace(|| "natural suit diff"),
+                suit_diff,
+                natural.clone(),
+            )?;
+            let suit_diff = gate_chip.mul(
+                layouter.namespace(|| "run suit diff"),
+                suit_diff,
+                is_run.clone(),
+            )?;
+            gate_chip.assert_constant(layouter.namespace(|| "run suit"), suit_diff, F::ZERO)?;
+
+            for (other, other_natural) in cards.iter().zip(naturals.iter()).take(i) {
+                let same_suit = gate_chip.is_equal(
+                    layouter.namespace(|| "same suit"),
+                    card.suit.clone(),
+                    other.suit.clone(),
+                )?;
+                let both = gate_chip.mul(
+                    layouter.namespace(|| "both natural"),
+                    natural.clone(),
+                    other_natural.clone(),
+                )?;
+                let clash =
+                    gate_chip.mul(layouter.namespace(|| "natural clash"), same_suit, both)?;
+                let clash = gate_chip.select(
+                    layouter.namespace(|| "set clash"),
+                    is_run.clone(),
+                    zero.clone(),
+                    clash,
+                )?;
+                gate_chip.assert_constant(layouter.namespace(|| "set suits"), clash, F::ZERO)?;
+            }
+
+            // the ace counts 1, faces 10 and the other cards rank + 2
+            let is_ace =
+                gate_chip.is_equal(layouter.namespace(|| "is ace"), rank.clone(), ace.clone())?;
+            let is_face = compare_chip.is_lt(
+                layouter.namespace(|| "is face or ace"),
+                ten.clone(),
+                rank.clone(),
+            )?;
+            let pips = gate_chip.add(layouter.namespace(|| "rank + 2"), rank, two.clone())?;
+            let face_points = gate_chip.select(
+                layouter.namespace(|| "face points"),
+                is_ace,
+                one.clone(),
+                ten_points.clone(),
+            )?;
+            let points =
+                gate_chip.select(layouter.namespace(|| "points"), is_face, face_points, pips)?;
+            score = gate_chip.add(layouter.namespace(|| "score"), score, points)?;
+        }
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(meld_commitment.cell(), config.instance, 0)?;
+        layouter.constrain_instance(score.cell(), config.instance, 1)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 2)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const MELD_SIZE: usize> CircuitExt<F> for MeldCircuit<F, MELD_SIZE> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            hand_commitment(self.context.session_id, self.meld, self.meld_salt),
+            F::from(self.score()),
+            self.context.digest(),
+        ]]
+    }
+}
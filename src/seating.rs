@@ -0,0 +1,90 @@
+use crate::{
+    context::GameContext,
+    merkle::MerkleTree,
+    poseidon_chip::{poseidon_tagged, IdentityCommit, TableNullifier},
+};
+use halo2_utils::FieldExt;
+use std::collections::BTreeMap;
+
+/// A player's private identity, only [`Identity::commitment`] is registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Identity<F: FieldExt> {
+    pub nullifier: F,
+    pub trapdoor: F,
+}
+
+impl<F: FieldExt> Identity<F> {
+    /// `Poseidon(nullifier, trapdoor)` in the [`IdentityCommit`] domain.
+    pub fn commitment(&self) -> F {
+        poseidon_tagged(IdentityCommit, [self.nullifier, self.trapdoor])
+    }
+
+    /// `Poseidon(nullifier, table_id)` in the [`TableNullifier`] domain, the same identity
+    /// always gets the same nullifier at a table and unlinkable nullifiers across tables.
+    pub fn table_nullifier(&self, table_id: F) -> F {
+        poseidon_tagged(TableNullifier, [self.nullifier, table_id])
+    }
+}
+
+/// Merkle tree of the registered identity commitments.
+pub fn members_tree<F: FieldExt>(identities: &[Identity<F>], depth: usize) -> MerkleTree<F> {
+    let commitments = identities
+        .iter()
+        .map(Identity::commitment)
+        .collect::<Vec<_>>();
+    MerkleTree::new(&commitments, depth)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeatingError {
+    SeatTaken,
+    /// The nullifier already holds a seat at the table.
+    AlreadySeated,
+}
+
+/// Seats of a private table, filled from `SeatingCircuit` proofs made for `context`. The
+/// caller verifies every proof against [`Seating::instances`].
+#[derive(Debug, Clone)]
+pub struct Seating<F: FieldExt> {
+    pub members_root: F,
+    pub table_id: F,
+    pub context: GameContext<F>,
+    seats: BTreeMap<u64, F>,
+}
+
+impl<F: FieldExt> Seating<F> {
+    pub fn new(members_root: F, table_id: F, context: GameContext<F>) -> Self {
+        Self {
+            members_root,
+            table_id,
+            context,
+            seats: BTreeMap::new(),
+        }
+    }
+
+    /// Public inputs a seating proof for `seat` must verify against.
+    pub fn instances(&self, seat: u64, nullifier: F) -> Vec<F> {
+        vec![
+            self.members_root,
+            self.table_id,
+            F::from(seat),
+            nullifier,
+            self.context.digest(),
+        ]
+    }
+
+    pub fn take_seat(&mut self, seat: u64, nullifier: F) -> Result<(), SeatingError> {
+        if self.seats.contains_key(&seat) {
+            return Err(SeatingError::SeatTaken);
+        }
+        if self.seats.values().any(|seated| *seated == nullifier) {
+            return Err(SeatingError::AlreadySeated);
+        }
+        self.seats.insert(seat, nullifier);
+        Ok(())
+    }
+
+    pub fn seats(&self) -> &BTreeMap<u64, F> {
+        &self.seats
+    }
+}
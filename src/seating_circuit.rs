@@ -0,0 +1,155 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    merkle::MerkleProof,
+    merkle_chip::MerkleChip,
+    poseidon_chip::{IdentityCommit, PoseidonChip, TableNullifier},
+    seating::Identity,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Takes a seat at a private table without revealing which registered player joined.
+///
+/// Proves that the identity commitment is a leaf of `members_root` and derives the
+/// per-table nullifier from the same identity. The seat is loaded and exposed next to
+/// the context digest so the proof cannot be replayed for another seat or session.
+///
+/// Instances are `[members_root, table_id, seat, nullifier, context_digest]`.
+#[derive(Clone)]
+pub struct SeatingCircuit<F: FieldExt, const DEPTH: usize> {
+    pub identity: Identity<F>,
+    pub position: usize,
+    pub siblings: [F; DEPTH],
+    pub table_id: F,
+    pub seat: u64,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const DEPTH: usize> SeatingCircuit<F, DEPTH> {
+    pub fn members_root(&self) -> F {
+        MerkleProof {
+            index: self.position,
+            siblings: self.siblings.to_vec(),
+        }
+        .root(self.identity.commitment())
+    }
+}
+
+#[derive(Clone)]
+pub struct SeatingCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const DEPTH: usize> Circuit<F> for SeatingCircuit<F, DEPTH> {
+    type Config = SeatingCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        SeatingCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let poseidon_chip = config.poseidon_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let merkle_chip = MerkleChip::from(gate_chip.clone(), poseidon_chip.clone());
+
+        let identity_nullifier = gate_chip.load_private(
+            layouter.namespace(|| "load identity nullifier"),
+            Value::known(self.identity.nullifier),
+        )?;
+        let trapdoor = gate_chip.load_private(
+            layouter.namespace(|| "load trapdoor"),
+            Value::known(self.identity.trapdoor),
+        )?;
+        let commitment = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "identity commitment"),
+            IdentityCommit,
+            [identity_nullifier.clone(), trapdoor],
+        )?;
+
+        let position = gate_chip.load_private(
+            layouter.namespace(|| "load position"),
+            Value::known(F::from(self.position as u64)),
+        )?;
+        let mut siblings = vec![];
+        for sibling in self.siblings {
+            siblings.push(
+                gate_chip
+                    .load_private(layouter.namespace(|| "load sibling"), Value::known(sibling))?,
+            );
+        }
+        let members_root = merkle_chip.compute_root(
+            layouter.namespace(|| "members root"),
+            commitment,
+            position,
+            &siblings,
+        )?;
+
+        let table_id = gate_chip.load_private(
+            layouter.namespace(|| "load table id"),
+            Value::known(self.table_id),
+        )?;
+        let nullifier = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "table nullifier"),
+            TableNullifier,
+            [identity_nullifier, table_id.clone()],
+        )?;
+
+        let seat = gate_chip.load_private(
+            layouter.namespace(|| "load seat"),
+            Value::known(F::from(self.seat)),
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(members_root.cell(), config.instance, 0)?;
+        layouter.constrain_instance(table_id.cell(), config.instance, 1)?;
+        layouter.constrain_instance(seat.cell(), config.instance, 2)?;
+        layouter.constrain_instance(nullifier.cell(), config.instance, 3)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 4)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const DEPTH: usize> CircuitExt<F> for SeatingCircuit<F, DEPTH> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            self.members_root(),
+            self.table_id,
+            F::from(self.seat),
+            self.identity.table_nullifier(self.table_id),
+            self.context.digest(),
+        ]]
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    card::{DeckSpec, NUM_RANKS, NUM_SUITS},
+    card_chip::CardConfig,
+    compare_chip::CompareChip,
+    context::{ContextChip, GameContext},
+    gate_chip::GateChip,
+    hand_chip::{hand_commitment, HandChip},
+    poker::best_of_seven,
+    poker_chip::PokerConfig,
+    poseidon_chip::PoseidonChip,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// Texas Hold'em showdown, proves the score of the best hand made of two committed
+/// hole cards and five public board cards. Cards are indices of a standard deck.
+///
+/// Instances are `[hand_commitment, board.., score, context_digest]`, see `crate::poker` for how the
+/// category and tie-break vector are encoded in the score.
+#[derive(Clone)]
+pub struct ShowdownCircuit<F: FieldExt> {
+    pub hole_cards: [u64; 2],
+    pub hand_salt: F,
+    pub board: [u64; 5],
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt> ShowdownCircuit<F> {
+    pub fn score(&self) -> u64 {
+        let cards = [self.hole_cards.as_slice(), self.board.as_slice()].concat();
+        best_of_seven(std::array::from_fn(|i| {
+            DeckSpec::STANDARD.card(cards[i]).unwrap()
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub struct ShowdownCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    card_config: CardConfig<F, NUM_SUITS, NUM_RANKS>,
+    poker_config: PokerConfig<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt> Circuit<F> for ShowdownCircuit<F> {
+    type Config = ShowdownCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let card_config = CardConfig::configure(meta, Some(advice));
+        let poker_config = PokerConfig::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        ShowdownCircuitConfig {
+            gate_chip,
+            card_config,
+            poker_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let card_chip = config
+            .card_config
+            .construct(layouter.namespace(|| "card chip"), gate_chip.clone())?;
+        let compare_chip = CompareChip::from(gate_chip.clone(), card_chip.rank_range());
+        let poker_chip = config.poker_config.construct(
+            layouter.namespace(|| "poker chip"),
+            gate_chip.clone(),
+            compare_chip,
+        )?;
+        let hand_chip = HandChip::from(config.poseidon_chip.clone());
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let mut hole_cards = vec![];
+        for card in self.hole_cards {
+            hole_cards.push(gate_chip.load_private(
+                layouter.namespace(|| "load hole card"),
+                Value::known(F::from(card)),
+            )?);
+        }
+        let hand_salt = gate_chip.load_private(
+            layouter.namespace(|| "load hand salt"),
+            Value::known(self.hand_salt),
+        )?;
+        let hand_commitment = hand_chip.commit(
+            layouter.namespace(|| "hand commitment"),
+            context.session_id.clone(),
+            [hole_cards[0].clone(), hole_cards[1].clone()],
+            hand_salt,
+        )?;
+
+        let mut board = vec![];
+        for card in self.board {
+            board.push(gate_chip.load_private(
+                layouter.namespace(|| "load board card"),
+                Value::known(F::from(card)),
+            )?);
+        }
+
+        let mut cards = vec![];
+        for card in hole_cards.iter().chain(board.iter()) {
+            cards.push(card_chip.decompose(layouter.namespace(|| "decompose"), card.clone())?);
+        }
+        let score = poker_chip.best_of_seven(
+            layouter.namespace(|| "best of seven"),
+            &cards.try_into().unwrap(),
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(hand_commitment.cell(), config.instance, 0)?;
+        for (i, card) in board.iter().enumerate() {
+            layouter.constrain_instance(card.cell(), config.instance, i + 1)?;
+        }
+        layouter.constrain_instance(score.cell(), config.instance, board.len() + 1)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, board.len() + 2)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> CircuitExt<F> for ShowdownCircuit<F> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        let mut values = vec![hand_commitment(
+            self.context.session_id,
+            self.hole_cards,
+            self.hand_salt,
+        )];
+        values.extend(self.board.map(F::from));
+        values.push(F::from(self.score()));
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
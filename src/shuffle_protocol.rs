@@ -0,0 +1,182 @@
+use crate::{
+    context::GameContext,
+    distinct_single_key::{decrypt_cards, pack_cards, unpack_cards},
+    evm::{gen_pk, gen_proof, gen_srs, verify},
+    shuffle_step_circuit::ShuffleStepCircuit,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        halo2curves::{
+            bn256::{Bn256, Fr, G1Affine},
+            ff::Field,
+        },
+        plonk::ProvingKey,
+        poly::kzg::commitment::ParamsKZG,
+    },
+    CircuitExt,
+};
+
+/// A simulated player's secrets for one shuffle.
+#[derive(Debug, Clone)]
+pub struct Player<const NUM_CARDS: usize> {
+    pub permutation: [usize; NUM_CARDS],
+    pub key: u64,
+    pub key_salt: Fr,
+}
+
+/// A proven shuffle step as published by a player.
+#[derive(Debug, Clone)]
+pub struct ShuffleStep {
+    pub instances: Vec<Fr>,
+    pub proof: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptError {
+    /// The input deck of a step is not the output deck of the step before it.
+    BrokenChain {
+        step: usize,
+    },
+    /// The step was proven for another session or move.
+    WrongContext {
+        step: usize,
+    },
+    InvalidProof {
+        step: usize,
+    },
+}
+
+/// Everything needed to check a sequential shuffle, the deck every player started from
+/// and their steps in order. Step `i` is proven for `context.advance(i)`.
+#[derive(Debug, Clone)]
+pub struct ShuffleTranscript {
+    pub context: GameContext<Fr>,
+    pub initial_deck: Vec<Fr>,
+    pub steps: Vec<ShuffleStep>,
+}
+
+/// Runs a mental poker shuffle in-process, every player permutes and encrypts the
+/// deck in turn and proves it with a [`ShuffleStepCircuit`].
+pub struct ShuffleProtocol<
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+> {
+    params: ParamsKZG<Bn256>,
+    pk: ProvingKey<G1Affine>,
+}
+
+impl<const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    ShuffleProtocol<NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    pub fn setup(k: u32) -> Self {
+        let params = gen_srs(k);
+        let pk = gen_pk(
+            &params,
+            &Self::step(
+                Self::initial_deck(),
+                &Self::identity(),
+                GameContext::new(Fr::ZERO, 0),
+            ),
+        );
+        Self { params, pk }
+    }
+
+    pub fn initial_deck() -> [u64; NUM_CARDS] {
+        std::array::from_fn(|i| i as u64)
+    }
+
+    fn identity() -> Player<NUM_CARDS> {
+        Player {
+            permutation: std::array::from_fn(|i| i),
+            key: 0,
+            key_salt: Fr::ZERO,
+        }
+    }
+
+    fn step(
+        input: [u64; NUM_CARDS],
+        player: &Player<NUM_CARDS>,
+        context: GameContext<Fr>,
+    ) -> ShuffleStepCircuit<Fr, NUM_CARDS, WORD_BYTES, FIELD_BYTES> {
+        ShuffleStepCircuit {
+            input,
+            permutation: player.permutation,
+            key: player.key,
+            key_salt: player.key_salt,
+            context,
+        }
+    }
+
+    fn num_slots() -> usize {
+        NUM_CARDS * WORD_BYTES / FIELD_BYTES + 1
+    }
+
+    /// Lets every player shuffle in turn, each step starts from the previous output and
+    /// takes the next move of `context`.
+    pub fn shuffle(
+        &self,
+        players: &[Player<NUM_CARDS>],
+        context: GameContext<Fr>,
+    ) -> ShuffleTranscript {
+        let mut deck = Self::initial_deck();
+        let mut steps = vec![];
+        for (i, player) in players.iter().enumerate() {
+            let circuit = Self::step(deck, player, context.advance(i as u64));
+            deck = circuit.output();
+
+            let instances = circuit.instances();
+            let proof = gen_proof(&self.params, &self.pk, circuit, instances.clone());
+            steps.push(ShuffleStep {
+                instances: instances[0].clone(),
+                proof,
+            });
+        }
+
+        ShuffleTranscript {
+            context,
+            initial_deck: pack_cards::<Fr, WORD_BYTES, FIELD_BYTES>(&Self::initial_deck()),
+            steps,
+        }
+    }
+
+    /// Checks that the steps chain from the initial deck, are bound to their move of the
+    /// transcript's context and that every proof is valid.
+    pub fn verify(&self, transcript: &ShuffleTranscript) -> Result<(), TranscriptError> {
+        let num_slots = Self::num_slots();
+        let mut deck = transcript.initial_deck.as_slice();
+        for (step, shuffle_step) in transcript.steps.iter().enumerate() {
+            let instances = &shuffle_step.instances;
+            if instances.len() != 2 * num_slots + 2 || &instances[..num_slots] != deck {
+                return Err(TranscriptError::BrokenChain { step });
+            }
+            if !transcript.context.advance(step as u64).check(instances) {
+                return Err(TranscriptError::WrongContext { step });
+            }
+            if !verify(
+                &self.params,
+                self.pk.get_vk(),
+                &[instances.as_slice()],
+                &shuffle_step.proof,
+            ) {
+                return Err(TranscriptError::InvalidProof { step });
+            }
+            deck = &instances[num_slots..2 * num_slots];
+        }
+        Ok(())
+    }
+
+    /// The shuffled deck once every player revealed their key.
+    pub fn open(transcript: &ShuffleTranscript, keys: &[u64]) -> Vec<u64> {
+        let num_slots = Self::num_slots();
+        let slots = transcript
+            .steps
+            .last()
+            .map_or(transcript.initial_deck.as_slice(), |step| {
+                &step.instances[num_slots..2 * num_slots]
+            });
+        let encrypted = unpack_cards::<Fr, WORD_BYTES, FIELD_BYTES>(slots, NUM_CARDS);
+        let key = keys.iter().sum::<u64>() % NUM_CARDS as u64;
+        decrypt_cards::<NUM_CARDS>(&encrypted, key)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    addmod_chip::AddModChip,
+    context::{ContextChip, GameContext},
+    deck_chip::DeckChip,
+    distinct_single_key::{encrypt_cards, key_commitment, pack_cards},
+    gate_chip::GateChip,
+    permutation_chip::PermutationConfig,
+    poseidon_chip::{KeyCommit, PoseidonChip},
+    range_chip::RangeConfig,
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// One player's turn of a sequential shuffle: the input deck is permuted and then
+/// encrypted with the player's DSK shift cipher key. Keys of all players add up, so the
+/// deck is opened with their sum once every key is revealed.
+///
+/// Decks are packed like DSK, instances are `[input slots.., output slots..,
+/// key_commitment, context_digest]`.
+#[derive(Clone)]
+pub struct ShuffleStepCircuit<
+    F: FieldExt,
+    const NUM_CARDS: usize,
+    const WORD_BYTES: usize,
+    const FIELD_BYTES: usize,
+> {
+    pub input: [u64; NUM_CARDS],
+    /// The shuffled deck holds `input[permutation[i]]` at position `i`.
+    pub permutation: [usize; NUM_CARDS],
+    pub key: u64,
+    pub key_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    ShuffleStepCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    pub fn output(&self) -> [u64; NUM_CARDS] {
+        encrypt_cards(self.permutation.map(|i| self.input[i]), self.key)
+    }
+}
+
+#[derive(Clone)]
+pub struct ShuffleStepCircuitConfig<F: FieldExt, const NUM_CARDS: usize> {
+    gate_chip: GateChip<F>,
+    range_config: RangeConfig<F, NUM_CARDS>,
+    permutation_config: PermutationConfig<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    Circuit<F> for ShuffleStepCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    type Config = ShuffleStepCircuitConfig<F, NUM_CARDS>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let range_config = RangeConfig::configure(meta, Some(advice));
+        let permutation_config = PermutationConfig::configure(meta);
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        ShuffleStepCircuitConfig {
+            gate_chip,
+            range_config,
+            permutation_config,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let range_chip = config
+            .range_config
+            .construct(layouter.namespace(|| "range chip"))?;
+        let deck_chip = DeckChip::<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>::from(
+            gate_chip.clone(),
+            range_chip.clone(),
+        );
+        let addmod_chip = AddModChip::from(gate_chip.clone(), range_chip);
+        let permutation_chip = config.permutation_config.construct();
+
+        let mut input_slots = vec![];
+        for slot in pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.input) {
+            input_slots.push(
+                gate_chip
+                    .load_private(layouter.namespace(|| "load input slot"), Value::known(slot))?,
+            );
+        }
+        let input = deck_chip.unpack_deck(layouter.namespace(|| "unpack input"), &input_slots)?;
+
+        let mut shuffled = vec![];
+        for i in self.permutation {
+            shuffled.push(gate_chip.load_private(
+                layouter.namespace(|| "load shuffled card"),
+                Value::known(F::from(self.input[i])),
+            )?);
+        }
+        permutation_chip.assert_permutation(layouter.namespace(|| "shuffle"), &input, &shuffled)?;
+
+        let key = gate_chip.load_private(
+            layouter.namespace(|| "load key"),
+            Value::known(F::from(self.key)),
+        )?;
+        let key_salt = gate_chip.load_private(
+            layouter.namespace(|| "load key salt"),
+            Value::known(self.key_salt),
+        )?;
+
+        let mut output = vec![];
+        for card in shuffled {
+            output.push(addmod_chip.addmod(
+                layouter.namespace(|| "card + key"),
+                card,
+                key.clone(),
+            )?);
+        }
+        let output_slots = deck_chip.pack_deck(layouter.namespace(|| "pack output"), &output)?;
+
+        let key_commitment = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "key commitment"),
+            KeyCommit,
+            [context.session_id.clone(), key, key_salt],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        for (i, slot) in input_slots.iter().chain(output_slots.iter()).enumerate() {
+            layouter.constrain_instance(slot.cell(), config.instance, i)?;
+        }
+        layouter.constrain_instance(
+            key_commitment.cell(),
+            config.instance,
+            input_slots.len() + output_slots.len(),
+        )?;
+        layouter.constrain_instance(
+            context_digest.cell(),
+            config.instance,
+            input_slots.len() + output_slots.len() + 1,
+        )?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, const NUM_CARDS: usize, const WORD_BYTES: usize, const FIELD_BYTES: usize>
+    CircuitExt<F> for ShuffleStepCircuit<F, NUM_CARDS, WORD_BYTES, FIELD_BYTES>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        let mut values = pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.input);
+        values.extend(pack_cards::<F, WORD_BYTES, FIELD_BYTES>(&self.output()));
+        values.push(key_commitment(
+            self.context.session_id,
+            self.key,
+            self.key_salt,
+        ));
+        values.push(self.context.digest());
+        vec![values]
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    babyjubjub::Point,
+    context::{ContextChip, GameContext},
+    ecc_chip::EccChip,
+    eddsa::Signature,
+    eddsa_chip::{AssignedSignature, EddsaChip},
+    gate_chip::GateChip,
+    poseidon_chip::{poseidon_tagged, CircomPoseidonChip, PoseidonChip, SignedAction},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+/// The message a player signs for `action`, `Poseidon(action, context.digest())` in the
+/// [`SignedAction`] domain, so a signature only holds for one move of one session.
+pub fn action_message<F: FieldExt>(action: F, context: &GameContext<F>) -> F {
+    poseidon_tagged(SignedAction, [action, context.digest()])
+}
+
+/// Proves that `action` was signed by the player's key for the context of the proof,
+/// without revealing the signature.
+///
+/// Instances are `[public_key.x, public_key.y, action, context_digest]`.
+#[derive(Clone)]
+pub struct SignedActionCircuit<F: FieldExt> {
+    pub public_key: Point<F>,
+    pub action: F,
+    pub signature: Signature<F>,
+    pub context: GameContext<F>,
+}
+
+#[derive(Clone)]
+pub struct SignedActionCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    circom_poseidon_chip: CircomPoseidonChip<F, 6, 5>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt> Circuit<F> for SignedActionCircuit<F> {
+    type Config = SignedActionCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+        let circom_poseidon_chip = CircomPoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        SignedActionCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            circom_poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let ecc_chip = EccChip::from(gate_chip.clone());
+        let eddsa_chip = EddsaChip::from(gate_chip.clone(), config.circom_poseidon_chip);
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+
+        let public_key = ecc_chip.load_private(
+            layouter.namespace(|| "load public key"),
+            Value::known(self.public_key),
+        )?;
+        let action = gate_chip.load_private(
+            layouter.namespace(|| "load action"),
+            Value::known(self.action),
+        )?;
+        let signature = AssignedSignature {
+            r: ecc_chip.load_private(
+                layouter.namespace(|| "load r"),
+                Value::known(self.signature.r),
+            )?,
+            s: gate_chip.load_private(
+                layouter.namespace(|| "load s"),
+                Value::known(self.signature.s),
+            )?,
+        };
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+        let message = config.poseidon_chip.hash_tagged(
+            layouter.namespace(|| "action message"),
+            SignedAction,
+            [action.clone(), context_digest.clone()],
+        )?;
+        eddsa_chip.verify(
+            layouter.namespace(|| "verify signature"),
+            public_key.clone(),
+            message,
+            signature,
+        )?;
+
+        layouter.constrain_instance(public_key.x.cell(), config.instance, 0)?;
+        layouter.constrain_instance(public_key.y.cell(), config.instance, 1)?;
+        layouter.constrain_instance(action.cell(), config.instance, 2)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 3)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> CircuitExt<F> for SignedActionCircuit<F> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            self.public_key.x,
+            self.public_key.y,
+            self.action,
+            self.context.digest(),
+        ]]
+    }
+}
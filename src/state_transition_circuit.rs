@@ -0,0 +1,159 @@
+use crate::{
+    context::{ContextChip, GameContext},
+    game_step::{state_hash, GameStep},
+    gate_chip::GateChip,
+    poseidon_chip::{PoseidonChip, StateHash, StateWords},
+};
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+use std::marker::PhantomData;
+
+/// Proves one step of a game, the private state behind `old_state_hash` goes to the
+/// state behind `new_state_hash` under `action` as defined by `G`. Chaining the proofs
+/// with [`crate::game_step::check_chain`] verifies a whole game.
+///
+/// Instances are `[old_state_hash, action, new_state_hash, context_digest]`.
+#[derive(Clone)]
+pub struct StateTransitionCircuit<F: FieldExt, G: GameStep<F, STATE_SIZE>, const STATE_SIZE: usize>
+{
+    pub game: G,
+    pub state: [F; STATE_SIZE],
+    pub state_salt: F,
+    pub action: F,
+    pub new_state_salt: F,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt, G: GameStep<F, STATE_SIZE>, const STATE_SIZE: usize>
+    StateTransitionCircuit<F, G, STATE_SIZE>
+{
+    pub fn new_state(&self) -> [F; STATE_SIZE] {
+        self.game.step(self.state, self.action)
+    }
+}
+
+#[derive(Clone)]
+pub struct StateTransitionCircuitConfig<F: FieldExt, G> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+    _marker: PhantomData<G>,
+}
+
+impl<F: FieldExt, G: GameStep<F, STATE_SIZE> + Clone, const STATE_SIZE: usize> Circuit<F>
+    for StateTransitionCircuit<F, G, STATE_SIZE>
+{
+    type Config = StateTransitionCircuitConfig<F, G>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        StateTransitionCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            instance,
+            _marker: PhantomData,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let poseidon_chip = config.poseidon_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let mut state = vec![];
+        for value in self.state {
+            state.push(
+                gate_chip.load_private(layouter.namespace(|| "load state"), Value::known(value))?,
+            );
+        }
+        let state: [_; STATE_SIZE] = state.try_into().unwrap();
+        let state_salt = gate_chip.load_private(
+            layouter.namespace(|| "load state salt"),
+            Value::known(self.state_salt),
+        )?;
+        let action = gate_chip.load_private(
+            layouter.namespace(|| "load action"),
+            Value::known(self.action),
+        )?;
+
+        let state_digest = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "hash state"),
+            StateWords,
+            state.clone(),
+        )?;
+        let old_state_hash = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "old state hash"),
+            StateHash,
+            [context.session_id.clone(), state_digest, state_salt],
+        )?;
+
+        let new_state = self.game.synthesize(
+            &gate_chip,
+            layouter.namespace(|| "game step"),
+            state,
+            action.clone(),
+        )?;
+
+        let new_state_salt = gate_chip.load_private(
+            layouter.namespace(|| "load new state salt"),
+            Value::known(self.new_state_salt),
+        )?;
+        let new_state_digest = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "hash new state"),
+            StateWords,
+            new_state,
+        )?;
+        let new_state_hash = poseidon_chip.hash_tagged(
+            layouter.namespace(|| "new state hash"),
+            StateHash,
+            [context.session_id.clone(), new_state_digest, new_state_salt],
+        )?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        layouter.constrain_instance(old_state_hash.cell(), config.instance, 0)?;
+        layouter.constrain_instance(action.cell(), config.instance, 1)?;
+        layouter.constrain_instance(new_state_hash.cell(), config.instance, 2)?;
+        layouter.constrain_instance(context_digest.cell(), config.instance, 3)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt, G: GameStep<F, STATE_SIZE> + Clone, const STATE_SIZE: usize> CircuitExt<F>
+    for StateTransitionCircuit<F, G, STATE_SIZE>
+{
+    fn instances(&self) -> Vec<Vec<F>> {
+        vec![vec![
+            state_hash(self.context.session_id, self.state, self.state_salt),
+            self.action,
+            state_hash(
+                self.context.session_id,
+                self.new_state(),
+                self.new_state_salt,
+            ),
+            self.context.digest(),
+        ]]
+    }
+}
@@ -0,0 +1,123 @@
+use halo2_utils::{
+    halo2_proofs::{
+        circuit::{SimpleFloorPlanner, Value},
+        plonk::{Circuit, Column, Instance},
+    },
+    CircuitExt, FieldExt,
+};
+
+use crate::{
+    babyjubjub::Point,
+    context::{ContextChip, GameContext},
+    ecc_chip::EccChip,
+    gate_chip::GateChip,
+    poseidon_chip::PoseidonChip,
+};
+
+/// Proves that an unmasking share is correct, i.e. the discrete-log equality
+/// `log_G(public_key) == log_c1(share)`. The equality is checked directly in the
+/// circuit instead of verifying a Chaum–Pedersen transcript, which would need
+/// arithmetic modulo the subgroup order.
+///
+/// Instances are `[public_key, c1, share, context_digest]` with points as `x, y`.
+#[derive(Clone)]
+pub struct UnmaskShareCircuit<F: FieldExt> {
+    pub secret_key: F,
+    pub c1: Point<F>,
+    pub context: GameContext<F>,
+}
+
+impl<F: FieldExt> UnmaskShareCircuit<F> {
+    pub fn public_key(&self) -> Point<F> {
+        Point::generator().mul(self.secret_key)
+    }
+
+    pub fn share(&self) -> Point<F> {
+        self.c1.mul(self.secret_key)
+    }
+}
+
+#[derive(Clone)]
+pub struct UnmaskShareCircuitConfig<F: FieldExt> {
+    gate_chip: GateChip<F>,
+    poseidon_chip: PoseidonChip<F, 2>,
+    instance: Column<Instance>,
+}
+
+impl<F: FieldExt> Circuit<F> for UnmaskShareCircuit<F> {
+    type Config = UnmaskShareCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        todo!()
+    }
+
+    fn configure(meta: &mut halo2_utils::halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+
+        let gate_chip = GateChip::configure(meta, Some(advice));
+        let poseidon_chip = PoseidonChip::configure(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        UnmaskShareCircuitConfig {
+            gate_chip,
+            poseidon_chip,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_utils::halo2_proofs::circuit::Layouter<F>,
+    ) -> Result<(), halo2_utils::halo2_proofs::plonk::Error> {
+        let gate_chip = config.gate_chip;
+        let context_chip = ContextChip::from(gate_chip.clone(), config.poseidon_chip.clone());
+        let context = context_chip.load(layouter.namespace(|| "load context"), &self.context)?;
+
+        let ecc_chip = EccChip::from(gate_chip.clone());
+
+        let secret_key = gate_chip.load_private(
+            layouter.namespace(|| "load secret key"),
+            Value::known(self.secret_key),
+        )?;
+        let c1 = ecc_chip.load_private(layouter.namespace(|| "load c1"), Value::known(self.c1))?;
+
+        let generator =
+            ecc_chip.load_constant(layouter.namespace(|| "generator"), Point::generator())?;
+        let bits = gate_chip.to_bits(
+            layouter.namespace(|| "secret key bits"),
+            secret_key,
+            F::NUM_BITS as usize,
+        )?;
+        let public_key = ecc_chip.mul(layouter.namespace(|| "sk G"), generator, &bits)?;
+        let share = ecc_chip.mul(layouter.namespace(|| "sk c1"), c1.clone(), &bits)?;
+        let context_digest = context_chip.digest(layouter.namespace(|| "context"), &context)?;
+
+        for (i, point) in [public_key, c1, share].iter().enumerate() {
+            layouter.constrain_instance(point.x.cell(), config.instance, 2 * i)?;
+            layouter.constrain_instance(point.y.cell(), config.instance, 2 * i + 1)?;
+        }
+        layouter.constrain_instance(context_digest.cell(), config.instance, 6)?;
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> CircuitExt<F> for UnmaskShareCircuit<F> {
+    fn instances(&self) -> Vec<Vec<F>> {
+        let public_key = self.public_key();
+        let share = self.share();
+        vec![vec![
+            public_key.x,
+            public_key.y,
+            self.c1.x,
+            self.c1.y,
+            share.x,
+            share.y,
+            self.context.digest(),
+        ]]
+    }
+}
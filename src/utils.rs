@@ -1,4 +1,4 @@
-use halo2_utils::ethers::utils::keccak256;
+use halo2_utils::{ethers::utils::keccak256, FieldExt};
 
 pub fn randomize<const N: usize>(mut arr: [u64; N], rounds: usize) -> [u64; N] {
     let mut seed = [0; 32];
@@ -10,3 +10,21 @@ pub fn randomize<const N: usize>(mut arr: [u64; N], rounds: usize) -> [u64; N] {
     }
     arr
 }
+
+/// Reads the low 64 bits of a field element. Only meaningful for values that are
+/// known to be small, e.g. card indices.
+pub fn fe_to_u64<F: FieldExt>(value: F) -> u64 {
+    let repr = value.to_repr();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&repr.as_ref()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Little-endian bits of a field element, `F::NUM_BITS` of them.
+pub fn fe_to_bits<F: FieldExt>(value: F) -> Vec<bool> {
+    let repr = value.to_repr();
+    let bytes = repr.as_ref();
+    (0..F::NUM_BITS as usize)
+        .map(|i| bytes[i / 8] >> (i % 8) & 1 == 1)
+        .collect()
+}